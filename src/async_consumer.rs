@@ -0,0 +1,61 @@
+use core::future::{self, Future};
+
+use crate::consumer::Consumer;
+
+/// A sequence manipulator that moves items into a sequence, front to back, asynchronously.
+///
+/// The asynchronous counterpart of [`Consumer`], with the same rules about internal state changes
+/// and `close`. Implementations can use `async fn` for the methods.
+pub trait AsyncConsumer {
+    /// The type of the items in the sequence.
+    type Item;
+    /// Information about why `close` is being called.
+    type Ex;
+    /// Information about an internal state change.
+    type In;
+
+    /// Moves an item into the sequence at the current position and advances the position.
+    fn consume(&mut self, item: Self::Item) -> impl Future<Output = Result<(), Self::In>>;
+
+    /// Flushes all buffered items.
+    fn flush(&mut self) -> impl Future<Output = Result<(), Self::In>>;
+
+    /// Notifies the consumer that `consume` will not be called anymore.
+    fn close(&mut self, reason: Self::Ex) -> impl Future<Output = Result<(), Self::In>>;
+}
+
+/// Wraps a [`Consumer`] as an [`AsyncConsumer`] whose futures are ready immediately.
+///
+/// The wrapped consumer is called when a method is called, not when the future is polled.
+#[derive(Debug, Clone)]
+pub struct SyncAsConsumer<C> {
+    consumer: C,
+}
+
+impl<C> SyncAsConsumer<C> {
+    pub fn new(consumer: C) -> Self {
+        SyncAsConsumer { consumer }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.consumer
+    }
+}
+
+impl<C: Consumer> AsyncConsumer for SyncAsConsumer<C> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: C::Item) -> impl Future<Output = Result<(), C::In>> {
+        future::ready(self.consumer.consume(item))
+    }
+
+    fn flush(&mut self) -> impl Future<Output = Result<(), C::In>> {
+        future::ready(self.consumer.flush())
+    }
+
+    fn close(&mut self, reason: C::Ex) -> impl Future<Output = Result<(), C::In>> {
+        future::ready(self.consumer.close(reason))
+    }
+}
@@ -0,0 +1,61 @@
+use core::future::{self, Future};
+
+use crate::producer::Producer;
+
+/// A sequence manipulator that moves items out of a sequence, front to back, asynchronously.
+///
+/// The asynchronous counterpart of [`Producer`], with the same rules about internal state changes
+/// and `stop`. Implementations can use `async fn` for the methods.
+pub trait AsyncProducer {
+    /// The type of the items in the sequence.
+    type Item;
+    /// Information about why `stop` is being called.
+    type Ex;
+    /// Information about an internal state change.
+    type In;
+
+    /// Moves the item at the current position out of the sequence and advances the position.
+    fn produce(&mut self) -> impl Future<Output = Result<Self::Item, Self::In>>;
+
+    /// Moves items into an internal buffer, even if that buffer is not empty.
+    fn slurp(&mut self) -> impl Future<Output = Result<(), Self::In>>;
+
+    /// Notifies the producer that `produce` will not be called anymore.
+    fn stop(&mut self, reason: Self::Ex) -> impl Future<Output = Result<(), Self::In>>;
+}
+
+/// Wraps a [`Producer`] as an [`AsyncProducer`] whose futures are ready immediately.
+///
+/// The wrapped producer is called when a method is called, not when the future is polled.
+#[derive(Debug, Clone)]
+pub struct SyncAsProducer<P> {
+    producer: P,
+}
+
+impl<P> SyncAsProducer<P> {
+    pub fn new(producer: P) -> Self {
+        SyncAsProducer { producer }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer> AsyncProducer for SyncAsProducer<P> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> impl Future<Output = Result<P::Item, P::In>> {
+        future::ready(self.producer.produce())
+    }
+
+    fn slurp(&mut self) -> impl Future<Output = Result<(), P::In>> {
+        future::ready(self.producer.slurp())
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> impl Future<Output = Result<(), P::In>> {
+        future::ready(self.producer.stop(reason))
+    }
+}
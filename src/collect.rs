@@ -0,0 +1,60 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::producer::Producer;
+
+/// A type that can be created from the items of a producer, analogous to
+/// [`core::iter::FromIterator`].
+pub trait FromProducer<T>: Sized {
+    /// Creates a value from the items the producer produces before its next internal state
+    /// change. The internal state change is discarded.
+    fn from_producer<P: Producer<Item = T>>(producer: &mut P) -> Self;
+}
+
+/// Creates a value from the items the producer produces before its next internal state change.
+///
+/// The producer is borrowed, so it can be used further after the internal state change, e.g.
+/// `let v: Vec<u8> = collect(&mut producer);`.
+pub fn collect<P: Producer, B: FromProducer<P::Item>>(producer: &mut P) -> B {
+    B::from_producer(producer)
+}
+
+#[cfg(feature = "alloc")]
+impl<T> FromProducer<T> for Vec<T> {
+    fn from_producer<P: Producer<Item = T>>(producer: &mut P) -> Self {
+        let mut items = Vec::new();
+        while let Ok(item) = producer.produce() {
+            items.push(item);
+        }
+        items
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn collect_then_reuse_producer() {
+        let mut producer = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::StateChange("first"),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::StateChange("second"),
+            ProduceOutcome::StateChange("third"),
+        ]);
+
+        let first: Vec<u8> = collect(&mut producer);
+        assert_eq!(first, vec![1, 2]);
+        let second: Vec<u8> = collect(&mut producer);
+        assert_eq!(second, vec![3]);
+        let third = Vec::from_producer(&mut producer);
+        assert_eq!(third, Vec::<u8>::new());
+        assert_eq!(producer.remaining_script(), 0);
+    }
+}
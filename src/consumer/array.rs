@@ -0,0 +1,286 @@
+use core::cmp;
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1, SizedConsumer};
+
+/// The internal state change of an [`ArrayConsumer`] that is full, carrying the item that could
+/// not be consumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArrayFull<T>(pub T);
+
+/// Consumes items by moving them into an array, front to back, without allocating.
+///
+/// Once all `N` slots have been filled, `consume` returns the item in `Err(ArrayFull(item))`.
+/// `flush` and `close` do not do anything, the array can be retrieved with
+/// [`finish`](ArrayConsumer::finish).
+pub struct ArrayConsumer<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    // The items in `..len` are initialized.
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayConsumer<T, N> {
+    pub fn new() -> Self {
+        ArrayConsumer {
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Returns how many items have been consumed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns how many more items can be consumed.
+    pub fn remaining(&self) -> usize {
+        N - self.len
+    }
+
+    /// Returns the items consumed so far.
+    pub fn as_slice(&self) -> &[T] {
+        let filled = &self.items[..self.len];
+        unsafe { &*(filled as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let filled = &mut self.items[..self.len];
+        unsafe { &mut *(filled as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+
+    /// Returns the array if all `N` items have been consumed, or gives back the consumer
+    /// otherwise, whose [`len`](ArrayConsumer::len) and [`as_slice`](ArrayConsumer::as_slice)
+    /// describe the partially filled prefix.
+    pub fn finish(self) -> Result<[T; N], Self> {
+        if !self.is_full() {
+            return Err(self);
+        }
+
+        let this = ManuallyDrop::new(self);
+        Ok(unsafe { ptr::read(&this.items as *const [MaybeUninit<T>; N] as *const [T; N]) })
+    }
+}
+
+impl<T, const N: usize> Consumer for ArrayConsumer<T, N> {
+    type Item = T;
+    type Ex = ();
+    type In = ArrayFull<T>;
+
+    fn consume(&mut self, item: T) -> Result<(), ArrayFull<T>> {
+        if self.is_full() {
+            return Err(ArrayFull(item));
+        }
+
+        self.items[self.len] = MaybeUninit::new(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Copies as many items as fit into the array. If none fit, the first item is returned in the
+    /// internal state change.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, ArrayFull<T>>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = cmp::min(items.len(), self.remaining());
+        if amount == 0 {
+            return Err(ArrayFull(items[0]));
+        }
+
+        for (slot, item) in self.items[self.len..self.len + amount]
+            .iter_mut()
+            .zip(items)
+        {
+            *slot = MaybeUninit::new(*item);
+        }
+        self.len += amount;
+        Ok(unsafe { NonZeroUsize::new_unchecked(amount) })
+    }
+
+    fn flush(&mut self) -> Result<(), ArrayFull<T>> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), ArrayFull<T>> {
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> SizedConsumer for ArrayConsumer<T, N> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+/// Moves as many items of the loaf as fit into the array. If none fit, the first item is returned
+/// in the internal state change.
+impl<T, const N: usize> ConsumerFromMany1 for ArrayConsumer<T, N> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<T>,
+    ) -> Result<NonZeroUsize, ArrayFull<T>> {
+        let amount = cmp::min((*items).len(), self.remaining());
+        if amount == 0 {
+            return Err(ArrayFull(ptr::read(items as *const T)));
+        }
+
+        let end = self.items.as_mut_ptr().add(self.len) as *mut T;
+        ptr::copy_nonoverlapping(items as *const T, end, amount);
+        self.len += amount;
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+impl<T, const N: usize> Default for ArrayConsumer<T, N> {
+    fn default() -> Self {
+        ArrayConsumer::new()
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayConsumer<T, N> {
+    fn drop(&mut self) {
+        let filled = &mut self.items[..self.len];
+        self.len = 0;
+        unsafe { ptr::drop_in_place(filled as *mut [MaybeUninit<T>] as *mut [T]) };
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayConsumer<T, N> {
+    fn clone(&self) -> Self {
+        let mut clone = ArrayConsumer::new();
+        for item in self.as_slice() {
+            clone.items[clone.len] = MaybeUninit::new(item.clone());
+            clone.len += 1;
+        }
+        clone
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayConsumer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayConsumer")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::testing::DropCounted;
+
+    // Moves the items of `items` into the consumer with `consume_from_many1`, then drops the items
+    // that have not been read. Returns what `consume_from_many1` returned.
+    fn move_from<'a, const N: usize, const M: usize>(
+        consumer: &mut ArrayConsumer<DropCounted<'a, usize>, N>,
+        items: [DropCounted<'a, usize>; M],
+    ) -> Result<NonZeroUsize, ArrayFull<DropCounted<'a, usize>>> {
+        let mut items = ManuallyDrop::new(items);
+        let loaf = ptr::slice_from_raw_parts(items.as_ptr(), M - 1) as *const Loaf<_>;
+        let result = unsafe { consumer.consume_from_many1(loaf) };
+        let read = match &result {
+            Ok(amount) => amount.get(),
+            Err(_) => 1,
+        };
+        unsafe { ptr::drop_in_place(&mut items[read..]) };
+        result
+    }
+
+    #[test]
+    fn finish_full_and_partial() {
+        let mut consumer = ArrayConsumer::<u8, 2>::new();
+        assert_eq!(consumer.consume(1), Ok(()));
+        let consumer = consumer.finish().unwrap_err();
+        assert_eq!(consumer.as_slice(), [1]);
+
+        let mut consumer = consumer;
+        assert_eq!(consumer.consume(2), Ok(()));
+        assert!(consumer.is_full());
+        assert_eq!(consumer.finish().ok(), Some([1, 2]));
+    }
+
+    #[test]
+    fn consume_when_full_returns_item() {
+        let mut consumer = ArrayConsumer::<u8, 1>::new();
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.consume(2), Err(ArrayFull(2)));
+        assert_eq!(consumer.as_slice(), [1]);
+    }
+
+    #[test]
+    fn consume_many_caps_at_remaining() {
+        let mut consumer = ArrayConsumer::<u8, 4>::new();
+        assert_eq!(consumer.consume_many(&[1]).map(NonZeroUsize::get), Ok(1));
+        assert_eq!(
+            consumer.consume_many(&[2, 3, 4, 5]).map(NonZeroUsize::get),
+            Ok(3)
+        );
+        assert_eq!(consumer.remaining(), 0);
+        assert_eq!(consumer.consume_many(&[6, 7]), Err(ArrayFull(6)));
+        assert_eq!(consumer.as_slice(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn consume_from_many1_caps_at_remaining() {
+        let drops = Cell::new(0);
+        let mut consumer = ArrayConsumer::<_, 3>::new();
+
+        let items = core::array::from_fn::<_, 5, _>(|i| DropCounted::new(i, &drops));
+        assert_eq!(
+            move_from(&mut consumer, items).map(NonZeroUsize::get),
+            Ok(3)
+        );
+        // The two items that did not fit are still owned by the caller.
+        assert_eq!(drops.get(), 2);
+        assert_eq!(consumer.remaining(), 0);
+
+        let items = core::array::from_fn::<_, 2, _>(|i| DropCounted::new(10 + i, &drops));
+        match move_from(&mut consumer, items) {
+            Err(ArrayFull(item)) => assert_eq!(item.value, 10),
+            Ok(_) => panic!("consumed items although the array is full"),
+        }
+        assert_eq!(drops.get(), 4);
+
+        let values: [usize; 3] = core::array::from_fn(|i| consumer.as_slice()[i].value);
+        assert_eq!(values, [0, 1, 2]);
+        drop(consumer);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn drop_drops_only_consumed_items() {
+        let drops = Cell::new(0);
+        let mut consumer = ArrayConsumer::<_, 4>::new();
+        for i in 0..2 {
+            assert!(consumer.consume(DropCounted::new(i, &drops)).is_ok());
+        }
+        drop(consumer);
+        assert_eq!(drops.get(), 2);
+
+        let mut consumer = ArrayConsumer::<_, 2>::new();
+        for i in 0..2 {
+            assert!(consumer.consume(DropCounted::new(i, &drops)).is_ok());
+        }
+        let items = consumer.finish().ok().unwrap();
+        assert_eq!(drops.get(), 2);
+        drop(items);
+        assert_eq!(drops.get(), 4);
+    }
+}
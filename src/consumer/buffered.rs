@@ -0,0 +1,217 @@
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+
+use crate::consumer::Consumer;
+
+/// Collects items in an inline array of `N` items, passing them on to the wrapped consumer only
+/// once the array is full, or on `flush` or `close`.
+///
+/// If the wrapped consumer performs an internal state change while the buffer is being passed on,
+/// all items remaining in the buffer are dropped, [`dropped`](BufferedConsumer::dropped) reports
+/// how many. Dropping the `BufferedConsumer` drops all buffered items without passing them on.
+pub struct BufferedConsumer<C: Consumer, const N: usize> {
+    consumer: C,
+    // The items in `start..end` are initialized, all others have been moved out or dropped.
+    buf: [MaybeUninit<C::Item>; N],
+    start: usize,
+    end: usize,
+    dropped: usize,
+}
+
+impl<C: Consumer, const N: usize> BufferedConsumer<C, N> {
+    /// Panics if `N` is zero.
+    pub fn new(consumer: C) -> Self {
+        assert!(N > 0, "BufferedConsumer requires a nonzero capacity");
+        BufferedConsumer {
+            consumer,
+            buf: [const { MaybeUninit::uninit() }; N],
+            start: 0,
+            end: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Returns the items that have been buffered but not passed on yet.
+    pub fn buffer(&self) -> &[C::Item] {
+        let buffered = &self.buf[self.start..self.end];
+        unsafe { &*(buffered as *const [MaybeUninit<C::Item>] as *const [C::Item]) }
+    }
+
+    /// Returns how many buffered items were dropped after the most recent internal state change of
+    /// the wrapped consumer.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns the wrapped consumer. Buffered items are dropped without being passed on.
+    pub fn into_inner(self) -> C {
+        let mut this = ManuallyDrop::new(self);
+        this.drop_buffered();
+        unsafe { ptr::read(&this.consumer) }
+    }
+
+    // Passes all buffered items on to the wrapped consumer.
+    fn drain(&mut self) -> Result<(), C::In> {
+        while self.start < self.end {
+            let item = unsafe { self.buf[self.start].assume_init_read() };
+            self.start += 1;
+            if let Err(e) = self.consumer.consume(item) {
+                self.dropped = self.end - self.start;
+                self.drop_buffered();
+                return Err(e);
+            }
+        }
+
+        self.start = 0;
+        self.end = 0;
+        Ok(())
+    }
+
+    fn drop_buffered(&mut self) {
+        let buffered = &mut self.buf[self.start..self.end];
+        self.start = 0;
+        self.end = 0;
+        unsafe { ptr::drop_in_place(buffered as *mut [MaybeUninit<C::Item>] as *mut [C::Item]) };
+    }
+}
+
+impl<C: Consumer, const N: usize> Consumer for BufferedConsumer<C, N> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    /// Buffers the item, then passes on the whole buffer if it is full. An internal state change
+    /// of the wrapped consumer while doing so is returned, even though the item itself has been
+    /// accepted.
+    fn consume(&mut self, item: C::Item) -> Result<(), C::In> {
+        self.buf[self.end] = MaybeUninit::new(item);
+        self.end += 1;
+        if self.end == N {
+            self.drain()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        self.drain()?;
+        self.consumer.flush()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        self.drain()?;
+        self.consumer.close(reason)
+    }
+}
+
+impl<C: Consumer, const N: usize> Drop for BufferedConsumer<C, N> {
+    fn drop(&mut self) {
+        self.drop_buffered();
+    }
+}
+
+impl<C, const N: usize> fmt::Debug for BufferedConsumer<C, N>
+where
+    C: Consumer + fmt::Debug,
+    C::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedConsumer")
+            .field("consumer", &self.consumer)
+            .field("buffer", &self.buffer())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::testing::{ConsumerCall, ConsumerResponse, DropCounted, ScriptedConsumer};
+
+    #[test]
+    fn passes_items_on_only_when_full() {
+        let mut consumer = BufferedConsumer::<_, 3>::new(ScriptedConsumer::<u8, (), ()>::new([]));
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.consume(2), Ok(()));
+        assert_eq!(consumer.buffer(), [1, 2]);
+        assert!(consumer.consumer.log().is_empty());
+
+        assert_eq!(consumer.consume(3), Ok(()));
+        assert_eq!(consumer.buffer(), []);
+        assert_eq!(
+            consumer.consumer.log(),
+            [
+                ConsumerCall::Consume(1),
+                ConsumerCall::Consume(2),
+                ConsumerCall::Consume(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn flush_drains_partial_buffer() {
+        let mut consumer = BufferedConsumer::<_, 4>::new(ScriptedConsumer::<u8, (), ()>::new([]));
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.flush(), Ok(()));
+        assert_eq!(consumer.buffer(), []);
+        assert_eq!(
+            consumer.into_inner().log(),
+            [ConsumerCall::Consume(1), ConsumerCall::Flush]
+        );
+    }
+
+    #[test]
+    fn close_drains_then_closes() {
+        let mut consumer = BufferedConsumer::<_, 4>::new(ScriptedConsumer::<u8, (), &str>::new([]));
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.consume(2), Ok(()));
+        assert_eq!(consumer.close("done"), Ok(()));
+        assert_eq!(
+            consumer.into_inner().log(),
+            [
+                ConsumerCall::Consume(1),
+                ConsumerCall::Consume(2),
+                ConsumerCall::Close("done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn state_change_while_draining_drops_rest() {
+        let drops = Cell::new(0);
+        let inner = ScriptedConsumer::<_, &str, ()>::new([
+            ConsumerResponse::Accept,
+            ConsumerResponse::StateChange("full"),
+        ]);
+        let mut consumer = BufferedConsumer::<_, 4>::new(inner);
+
+        for i in 0..3 {
+            assert_eq!(consumer.consume(DropCounted::new(i, &drops)), Ok(()));
+        }
+        assert_eq!(consumer.consume(DropCounted::new(3, &drops)), Err("full"));
+        assert_eq!(consumer.dropped(), 2);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(consumer.buffer().len(), 0);
+
+        // The first two items have been moved into the wrapped consumer.
+        let inner = consumer.into_inner();
+        assert_eq!(drops.get(), 2);
+        assert_eq!(inner.log().len(), 2);
+        drop(inner);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn drop_drops_buffered_items() {
+        let drops = Cell::new(0);
+        let mut consumer = BufferedConsumer::<_, 4>::new(ScriptedConsumer::<_, (), ()>::new([]));
+        for i in 0..3 {
+            assert_eq!(consumer.consume(DropCounted::new(i, &drops)), Ok(()));
+        }
+        drop(consumer);
+        assert_eq!(drops.get(), 3);
+    }
+}
@@ -0,0 +1,303 @@
+use core::hash::Hasher;
+use core::num::NonZeroUsize;
+
+use crate::consumer::Consumer;
+
+/// A [`Hasher`] that computes the XOR of all bytes it is fed.
+///
+/// `finish` returns the XOR as a `u64` whose upper seven bytes are zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct XorHasher(u8);
+
+impl XorHasher {
+    pub fn new() -> Self {
+        XorHasher(0)
+    }
+}
+
+impl Hasher for XorHasher {
+    fn finish(&self) -> u64 {
+        u64::from(self.0)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= byte;
+        }
+    }
+}
+
+/// A [`Hasher`] that computes the CRC-32 (as used by zlib, PNG and Ethernet) of all bytes it is
+/// fed.
+///
+/// `finish` returns the CRC as a `u64` whose upper four bytes are zero.
+#[cfg(feature = "crc32")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crc32Hasher {
+    // The running CRC, before the final inversion.
+    state: u32,
+}
+
+#[cfg(feature = "crc32")]
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+#[cfg(feature = "crc32")]
+impl Crc32Hasher {
+    pub fn new() -> Self {
+        Crc32Hasher { state: !0 }
+    }
+}
+
+#[cfg(feature = "crc32")]
+impl Default for Crc32Hasher {
+    fn default() -> Self {
+        Crc32Hasher::new()
+    }
+}
+
+#[cfg(feature = "crc32")]
+impl Hasher for Crc32Hasher {
+    fn finish(&self) -> u64 {
+        u64::from(!self.state)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            let index = (self.state ^ u32::from(*byte)) & 0xFF;
+            self.state = (self.state >> 8) ^ CRC32_TABLE[index as usize];
+        }
+    }
+}
+
+/// Passes bytes on to the wrapped consumer while feeding them to a [`Hasher`], and appends the
+/// result of [`Hasher::finish`] as eight big-endian bytes on `close`.
+///
+/// The hasher only receives bytes the wrapped consumer accepted. Bytes are passed to
+/// [`Hasher::write`] in chunks of varying sizes, so the hasher's result must not depend on how its
+/// input is split across calls, as is the case for [`XorHasher`] and typical checksum hashers.
+/// [`ChecksumVerifyingProducer`](crate::producer::ChecksumVerifyingProducer) reads the result back.
+///
+/// `close` is an exception to the rule that an internal state change does not modify the
+/// sequence: if the wrapped consumer performs an internal state change while the checksum is
+/// being written, the bytes of the checksum written so far remain in it, and the wrapped consumer
+/// is not closed. The consumer keeps track of this, calling `close` again resumes with the first
+/// byte of the checksum that has not been written yet.
+#[derive(Debug, Clone)]
+pub struct ChecksumConsumer<C, H> {
+    consumer: C,
+    hasher: H,
+    // How many bytes of the checksum have been written by `close`.
+    checksum_written: usize,
+}
+
+impl<C, H: Hasher> ChecksumConsumer<C, H> {
+    pub fn new(consumer: C, hasher: H) -> Self {
+        ChecksumConsumer {
+            consumer,
+            hasher,
+            checksum_written: 0,
+        }
+    }
+
+    /// Returns the checksum of all bytes consumed so far.
+    pub fn checksum(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    pub fn into_inner(self) -> (C, H) {
+        (self.consumer, self.hasher)
+    }
+}
+
+impl<C: Consumer<Item = u8>, H: Hasher> Consumer for ChecksumConsumer<C, H> {
+    type Item = u8;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: u8) -> Result<(), C::In> {
+        self.consumer.consume(item)?;
+        self.hasher.write(&[item]);
+        Ok(())
+    }
+
+    fn consume_many(&mut self, items: &[u8]) -> Result<NonZeroUsize, C::In> {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = self.consumer.consume_many(items)?;
+        self.hasher.write(&items[..amount.get()]);
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        self.consumer.flush()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        let checksum = self.hasher.finish().to_be_bytes();
+        while self.checksum_written < checksum.len() {
+            let amount = self
+                .consumer
+                .consume_many(&checksum[self.checksum_written..])?;
+            self.checksum_written += amount.get();
+        }
+        self.consumer.close(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::consumer::{ArrayConsumer, SliceConsumer};
+    use crate::producer::{
+        ChecksumVerifyError, ChecksumVerifyingProducer, Producer, SliceEnd, SliceProducer,
+    };
+
+    const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    // Writes `DATA` with a checksum and reads it back, returning the decoded bytes and the final
+    // internal state change of the verifying producer.
+    fn round_trip<H: Hasher + Clone>(
+        hasher: H,
+        corrupt: Option<usize>,
+    ) -> (usize, ChecksumVerifyError<SliceEnd>) {
+        let mut buf = [0; 64];
+        let mut consumer = ChecksumConsumer::new(SliceConsumer::new(&mut buf), hasher.clone());
+        consumer.consume_many(&DATA[..10]).unwrap();
+        for byte in &DATA[10..] {
+            consumer.consume(*byte).unwrap();
+        }
+        consumer.close(()).unwrap();
+        let written = consumer.into_inner().0.into_filled();
+        assert_eq!(written.len(), DATA.len() + 8);
+        if let Some(i) = corrupt {
+            written[i] ^= 1;
+        }
+
+        let mut producer = ChecksumVerifyingProducer::new(SliceProducer::new(written), hasher);
+        let mut decoded = 0;
+        loop {
+            match producer.produce() {
+                Ok(byte) => {
+                    if corrupt.is_none() {
+                        assert_eq!(byte, DATA[decoded]);
+                    }
+                    decoded += 1;
+                }
+                Err(e) => return (decoded, e),
+            }
+        }
+    }
+
+    #[test]
+    fn xor_round_trip() {
+        assert_eq!(
+            round_trip(XorHasher::new(), None),
+            (DATA.len(), ChecksumVerifyError::Verified(SliceEnd))
+        );
+        assert!(matches!(
+            round_trip(XorHasher::new(), Some(3)),
+            (_, ChecksumVerifyError::Mismatch { .. })
+        ));
+        assert!(matches!(
+            round_trip(XorHasher::new(), Some(DATA.len() + 7)),
+            (_, ChecksumVerifyError::Mismatch { .. })
+        ));
+    }
+
+    #[cfg(feature = "crc32")]
+    #[test]
+    fn crc32_round_trip() {
+        let mut hasher = Crc32Hasher::new();
+        hasher.write(b"123456789");
+        assert_eq!(hasher.finish(), 0xCBF4_3926);
+
+        assert_eq!(
+            round_trip(Crc32Hasher::new(), None),
+            (DATA.len(), ChecksumVerifyError::Verified(SliceEnd))
+        );
+        assert!(matches!(
+            round_trip(Crc32Hasher::new(), Some(20)),
+            (_, ChecksumVerifyError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut producer =
+            ChecksumVerifyingProducer::new(SliceProducer::new(&[1, 2, 3]), XorHasher::new());
+        assert_eq!(
+            producer.produce(),
+            Err(ChecksumVerifyError::Truncated(SliceEnd))
+        );
+    }
+
+    // Accepts bytes as long as its budget lasts.
+    struct BudgetConsumer {
+        bytes: ArrayConsumer<u8, 16>,
+        budget: usize,
+    }
+
+    impl Consumer for BudgetConsumer {
+        type Item = u8;
+        type Ex = ();
+        type In = ();
+
+        fn consume(&mut self, item: u8) -> Result<(), ()> {
+            if self.budget == 0 {
+                return Err(());
+            }
+            self.budget -= 1;
+            self.bytes.consume(item).map_err(|_| ())
+        }
+
+        fn flush(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn close(&mut self, _reason: ()) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn close_resumes_writing_the_checksum() {
+        let inner = BudgetConsumer {
+            bytes: ArrayConsumer::new(),
+            budget: 6,
+        };
+        let mut consumer = ChecksumConsumer::new(inner, XorHasher::new());
+        consumer.consume_many(&[0x0F, 0xF0, 0x01]).unwrap();
+        assert_eq!(consumer.close(()), Err(()));
+        consumer.consumer.budget = 5;
+        assert_eq!(consumer.close(()), Ok(()));
+
+        assert_eq!(
+            consumer
+                .into_inner()
+                .0
+                .bytes
+                .finish()
+                .unwrap_err()
+                .as_slice(),
+            [0x0F, 0xF0, 0x01, 0, 0, 0, 0, 0, 0, 0, 0xFE]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use core::num::NonZeroUsize;
+
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1};
+
+/// The tallies of a [`CountingConsumer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ConsumerCounts {
+    /// How many items the wrapped consumer has accepted.
+    pub items: usize,
+    /// How many calls to `flush` have succeeded.
+    pub flushes: usize,
+    /// Whether `close` has been called, regardless of whether it succeeded.
+    pub closed: bool,
+}
+
+/// Passes everything on to the wrapped consumer, counting the consumed items and flushes and
+/// remembering whether it has been closed.
+///
+/// Items whose consumption results in an internal state change are not counted, neither are
+/// flushes that fail. Use [`CountingSinkConsumer`](crate::consumer::CountingSinkConsumer) if only
+/// the number of items is needed.
+#[derive(Debug, Clone)]
+pub struct CountingConsumer<C> {
+    consumer: C,
+    counts: ConsumerCounts,
+}
+
+impl<C> CountingConsumer<C> {
+    pub fn new(consumer: C) -> Self {
+        CountingConsumer {
+            consumer,
+            counts: ConsumerCounts::default(),
+        }
+    }
+
+    pub fn counts(&self) -> ConsumerCounts {
+        self.counts
+    }
+
+    /// Returns how many items have been consumed.
+    pub fn items(&self) -> usize {
+        self.counts.items
+    }
+
+    /// Returns how many times the consumer has been flushed.
+    pub fn flushes(&self) -> usize {
+        self.counts.flushes
+    }
+
+    /// Returns whether `close` has been called.
+    pub fn closed(&self) -> bool {
+        self.counts.closed
+    }
+
+    pub fn into_inner(self) -> (C, ConsumerCounts) {
+        (self.consumer, self.counts)
+    }
+}
+
+impl<C: Consumer> Consumer for CountingConsumer<C> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: C::Item) -> Result<(), C::In> {
+        self.consumer.consume(item)?;
+        self.counts.items += 1;
+        Ok(())
+    }
+
+    fn consume_many(&mut self, items: &[C::Item]) -> Result<NonZeroUsize, C::In>
+    where
+        C::Item: Copy,
+    {
+        let amount = self.consumer.consume_many(items)?;
+        self.counts.items += amount.get();
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        self.consumer.flush()?;
+        self.counts.flushes += 1;
+        Ok(())
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        self.counts.closed = true;
+        self.consumer.close(reason)
+    }
+}
+
+impl<C: ConsumerFromMany1> ConsumerFromMany1 for CountingConsumer<C> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<C::Item>,
+    ) -> Result<NonZeroUsize, C::In> {
+        let amount = self.consumer.consume_from_many1(items)?;
+        self.counts.items += amount.get();
+        Ok(amount)
+    }
+}
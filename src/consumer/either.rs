@@ -0,0 +1,72 @@
+use core::num::NonZeroUsize;
+
+use crate::consumer::Consumer;
+use crate::Either;
+
+/// One of two consumers with the same item and state change types, selected at runtime.
+///
+/// All method calls are forwarded to the selected consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EitherConsumer<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> EitherConsumer<L, R> {
+    pub fn into_inner(self) -> Either<L, R> {
+        match self {
+            EitherConsumer::Left(l) => Either::Left(l),
+            EitherConsumer::Right(r) => Either::Right(r),
+        }
+    }
+}
+
+impl<L, R> From<Either<L, R>> for EitherConsumer<L, R> {
+    fn from(either: Either<L, R>) -> Self {
+        match either {
+            Either::Left(l) => EitherConsumer::Left(l),
+            Either::Right(r) => EitherConsumer::Right(r),
+        }
+    }
+}
+
+impl<L, R> Consumer for EitherConsumer<L, R>
+where
+    L: Consumer,
+    R: Consumer<Item = L::Item, Ex = L::Ex, In = L::In>,
+{
+    type Item = L::Item;
+    type Ex = L::Ex;
+    type In = L::In;
+
+    fn consume(&mut self, item: L::Item) -> Result<(), L::In> {
+        match self {
+            EitherConsumer::Left(l) => l.consume(item),
+            EitherConsumer::Right(r) => r.consume(item),
+        }
+    }
+
+    fn consume_many(&mut self, items: &[L::Item]) -> Result<NonZeroUsize, L::In>
+    where
+        L::Item: Copy,
+    {
+        match self {
+            EitherConsumer::Left(l) => l.consume_many(items),
+            EitherConsumer::Right(r) => r.consume_many(items),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), L::In> {
+        match self {
+            EitherConsumer::Left(l) => l.flush(),
+            EitherConsumer::Right(r) => r.flush(),
+        }
+    }
+
+    fn close(&mut self, reason: L::Ex) -> Result<(), L::In> {
+        match self {
+            EitherConsumer::Left(l) => l.close(reason),
+            EitherConsumer::Right(r) => r.close(reason),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::consumer::Consumer;
+
+/// Accepts nothing, every call to `consume` drops the item and performs an internal state change
+/// with `In::default()`.
+///
+/// `flush` and `close` always succeed, and the consumer keeps behaving the same after an internal
+/// state change.
+pub struct EmptyConsumer<T, In = ()> {
+    _item: PhantomData<fn(T) -> In>,
+}
+
+impl<T, In> EmptyConsumer<T, In> {
+    pub fn new() -> Self {
+        EmptyConsumer { _item: PhantomData }
+    }
+}
+
+impl<T, In: Default> Consumer for EmptyConsumer<T, In> {
+    type Item = T;
+    type Ex = ();
+    type In = In;
+
+    fn consume(&mut self, _item: T) -> Result<(), In> {
+        Err(In::default())
+    }
+
+    fn flush(&mut self) -> Result<(), In> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), In> {
+        Ok(())
+    }
+}
+
+impl<T, In> Default for EmptyConsumer<T, In> {
+    fn default() -> Self {
+        EmptyConsumer::new()
+    }
+}
+
+impl<T, In> Clone for EmptyConsumer<T, In> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, In> Copy for EmptyConsumer<T, In> {}
+
+impl<T, In> fmt::Debug for EmptyConsumer<T, In> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EmptyConsumer")
+    }
+}
@@ -0,0 +1,79 @@
+use crate::consumer::{
+    Consumer, CountingConsumer, InspectConsumer, LimitConsumer, MapConsumer, MapErrConsumer,
+    PartitionConsumer, TeeConsumer,
+};
+
+/// Provided methods for all consumers.
+///
+/// These are kept out of [`Consumer`] so that the core trait stays small and object safe.
+pub trait ConsumerExt: Consumer {
+    /// Consumes an item, ignoring any internal state change. The item is lost if the consumer
+    /// does not accept it.
+    fn consume_or_ignore(&mut self, item: Self::Item) {
+        let _ = self.consume(item);
+    }
+
+    /// Counts the consumed items and flushes, and remembers whether the consumer has been closed.
+    fn counting(self) -> CountingConsumer<Self>
+    where
+        Self: Sized,
+    {
+        CountingConsumer::new(self)
+    }
+
+    /// Calls a function with a reference to every item before consuming it.
+    fn inspect<F: FnMut(&Self::Item)>(self, f: F) -> InspectConsumer<Self, F>
+    where
+        Self: Sized,
+    {
+        InspectConsumer::new(self, f)
+    }
+
+    /// Passes at most `n` items on to this consumer, performing an internal state change for all
+    /// further items.
+    fn limit(self, n: usize) -> LimitConsumer<Self>
+    where
+        Self: Sized,
+    {
+        LimitConsumer::new(self, n)
+    }
+
+    /// Applies a function to every item before consuming the result.
+    fn map_input<B, F>(self, f: F) -> MapConsumer<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(B) -> Self::Item,
+    {
+        MapConsumer::new(self, f)
+    }
+
+    /// Applies a function to every internal state change.
+    fn map_err<E, F: FnMut(Self::In) -> E>(self, f: F) -> MapErrConsumer<Self, F>
+    where
+        Self: Sized,
+    {
+        MapErrConsumer::new(self, f)
+    }
+
+    /// Passes the items that satisfy the predicate to this consumer, and all others to `other`.
+    fn partition<B, F>(self, other: B, predicate: F) -> PartitionConsumer<Self, B, F>
+    where
+        Self: Sized,
+        B: Consumer<Item = Self::Item>,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        PartitionConsumer::new(self, other, predicate)
+    }
+
+    /// Consumes every item with both this consumer (which receives a clone) and `other`.
+    fn tee<B>(self, other: B) -> TeeConsumer<Self, B>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        B: Consumer<Item = Self::Item>,
+    {
+        TeeConsumer::new(self, other)
+    }
+}
+
+impl<C: Consumer + ?Sized> ConsumerExt for C {}
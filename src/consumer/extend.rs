@@ -0,0 +1,78 @@
+use core::convert::Infallible;
+
+use alloc::vec::Vec;
+
+use crate::consumer::Consumer;
+
+const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// Consumes items by extending a collection with them, in batches.
+///
+/// Items are collected in an internal buffer, which is passed to [`Extend::extend`] when it is
+/// full, on `flush`, on `close`, and by `into_inner`. Never performs an internal state change.
+#[derive(Debug, Clone)]
+pub struct ExtendConsumer<C, T> {
+    collection: C,
+    batch: Vec<T>,
+    batch_size: usize,
+}
+
+impl<C: Extend<T>, T> ExtendConsumer<C, T> {
+    pub fn new(collection: C) -> Self {
+        ExtendConsumer::with_batch_size(DEFAULT_BATCH_SIZE, collection)
+    }
+
+    /// Creates a consumer that extends the collection once `batch_size` items have been consumed.
+    ///
+    /// Panics if `batch_size` is zero.
+    pub fn with_batch_size(batch_size: usize, collection: C) -> Self {
+        assert!(
+            batch_size > 0,
+            "ExtendConsumer requires a nonzero batch size"
+        );
+        ExtendConsumer {
+            collection,
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+        }
+    }
+
+    /// Returns the collection, without the items of the current batch.
+    pub fn get_ref(&self) -> &C {
+        &self.collection
+    }
+
+    /// Extends the collection with the current batch and returns it.
+    pub fn into_inner(mut self) -> C {
+        self.extend_batch();
+        self.collection
+    }
+
+    fn extend_batch(&mut self) {
+        self.collection.extend(self.batch.drain(..));
+    }
+}
+
+impl<C: Extend<T>, T> Consumer for ExtendConsumer<C, T> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn consume(&mut self, item: T) -> Result<(), Infallible> {
+        self.batch.push(item);
+        if self.batch.len() >= self.batch_size {
+            self.extend_batch();
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        self.extend_batch();
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Infallible> {
+        self.extend_batch();
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use core::fmt;
+
+use crate::consumer::Consumer;
+
+/// Consumes characters by writing them to a [`core::fmt::Write`].
+///
+/// An error of the writer is the internal state change. `flush` and `close` do not do anything.
+#[derive(Debug, Clone)]
+pub struct FmtWriteConsumer<W> {
+    writer: W,
+}
+
+impl<W> FmtWriteConsumer<W> {
+    pub fn new(writer: W) -> Self {
+        FmtWriteConsumer { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: fmt::Write> Consumer for FmtWriteConsumer<W> {
+    type Item = char;
+    type Ex = ();
+    type In = fmt::Error;
+
+    fn consume(&mut self, item: char) -> Result<(), fmt::Error> {
+        self.writer.write_char(item)
+    }
+
+    fn flush(&mut self) -> Result<(), fmt::Error> {
+        self.writer.write_str("")
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), fmt::Error> {
+        Ok(())
+    }
+}
+
+/// Consumes string slices by writing them to a [`core::fmt::Write`].
+///
+/// An error of the writer is the internal state change. `flush` and `close` do not do anything.
+#[derive(Debug, Clone)]
+pub struct FmtStrConsumer<W> {
+    writer: W,
+}
+
+impl<W> FmtStrConsumer<W> {
+    pub fn new(writer: W) -> Self {
+        FmtStrConsumer { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: fmt::Write> Consumer for FmtStrConsumer<W> {
+    type Item = &'static str;
+    type Ex = ();
+    type In = fmt::Error;
+
+    fn consume(&mut self, item: &'static str) -> Result<(), fmt::Error> {
+        self.writer.write_str(item)
+    }
+
+    fn flush(&mut self) -> Result<(), fmt::Error> {
+        self.writer.write_str("")
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), fmt::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+
+    use crate::pipe;
+    use crate::producer::CharProducer;
+    use crate::PipeError;
+
+    #[test]
+    fn writes_into_string() {
+        let mut producer = CharProducer::new("h€llo, wörld");
+        let mut consumer = FmtWriteConsumer::new(String::new());
+        assert_eq!(
+            pipe(&mut producer, &mut consumer),
+            Err(PipeError::Producer(()))
+        );
+        assert_eq!(consumer.flush(), Ok(()));
+        assert_eq!(consumer.close(()), Ok(()));
+        assert_eq!(consumer.into_inner(), "h€llo, wörld");
+    }
+
+    #[test]
+    fn writes_str_into_string() {
+        let mut consumer = FmtStrConsumer::new(String::new());
+        assert_eq!(consumer.consume("foo"), Ok(()));
+        assert_eq!(consumer.consume(""), Ok(()));
+        assert_eq!(consumer.consume("bär"), Ok(()));
+        assert_eq!(consumer.into_inner(), "foobär");
+    }
+}
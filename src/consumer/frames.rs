@@ -0,0 +1,265 @@
+use core::marker::PhantomData;
+
+use crate::consumer::{Consumer, SizedConsumer};
+use crate::producer::{LengthPrefix, MAX_PREFIX_LEN};
+
+/// The internal state change of a [`FramesConsumer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FramesConsumerError<I> {
+    /// The payload is too long for its length to be encoded in the prefix. Nothing has been
+    /// written.
+    TooLong { len: usize },
+    /// The wrapped consumer does not guarantee to accept all `needed` bytes of the frame (prefix
+    /// included). Nothing has been written.
+    InsufficientCapacity { needed: usize },
+    /// The wrapped consumer performed an internal state change after `written` bytes of the frame
+    /// (prefix included) had been consumed.
+    ///
+    /// `written` is only ever nonzero for consumers created with
+    /// [`FramesConsumer::allowing_partial_frames`], the wrapped consumer then holds an incomplete
+    /// frame. Internal state changes of `flush` and `close` are reported with a `written` of zero.
+    Inner { written: usize, inner: I },
+}
+
+/// Consumes payloads by writing them as length-prefixed frames into the wrapped consumer of bytes.
+///
+/// This is the counterpart of [`FramesProducer`](crate::producer::FramesProducer), frames written
+/// with the same [`LengthPrefix`] can be read back with it. `flush` and `close` are forwarded.
+///
+/// A consumer created with [`new`](FramesConsumer::new) only writes a frame if the
+/// [capacity hint](SizedConsumer::capacity_hint) of the wrapped consumer guarantees that it
+/// accepts the whole frame, so an internal state change never leaves a partial frame behind.
+/// Wrapped consumers that cannot give such a guarantee (such as writers to the network) can only
+/// be used by explicitly opting out of this with
+/// [`allowing_partial_frames`](FramesConsumer::allowing_partial_frames).
+#[derive(Debug, Clone)]
+pub struct FramesConsumer<'a, C, const PARTIAL_FRAMES: bool = false> {
+    consumer: C,
+    prefix: LengthPrefix,
+    _payload: PhantomData<&'a [u8]>,
+}
+
+impl<'a, C> FramesConsumer<'a, C> {
+    pub fn new(consumer: C, prefix: LengthPrefix) -> Self {
+        FramesConsumer {
+            consumer,
+            prefix,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<'a, C> FramesConsumer<'a, C, true> {
+    /// Creates a consumer that writes frames into any consumer of bytes, without checking its
+    /// capacity first.
+    ///
+    /// This breaks the usual guarantee that an internal state change does not modify the
+    /// sequence: if the wrapped consumer performs an internal state change in the middle of a
+    /// frame, the bytes written so far remain in it, as reported by
+    /// [`FramesConsumerError::Inner`].
+    pub fn allowing_partial_frames(consumer: C, prefix: LengthPrefix) -> Self {
+        FramesConsumer {
+            consumer,
+            prefix,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<'a, C, const PARTIAL_FRAMES: bool> FramesConsumer<'a, C, PARTIAL_FRAMES> {
+    pub fn into_inner(self) -> C {
+        self.consumer
+    }
+}
+
+impl<'a, C: Consumer<Item = u8>, const PARTIAL_FRAMES: bool> FramesConsumer<'a, C, PARTIAL_FRAMES> {
+    // Encodes the length prefix of a payload of length `len`.
+    fn encode_prefix(
+        &self,
+        len: usize,
+    ) -> Result<([u8; MAX_PREFIX_LEN], usize), FramesConsumerError<C::In>> {
+        let mut prefix = [0; MAX_PREFIX_LEN];
+        match self.prefix.encode(len as u64, &mut prefix) {
+            Some(prefix_len) => Ok((prefix, prefix_len)),
+            None => Err(FramesConsumerError::TooLong { len }),
+        }
+    }
+
+    fn write_frame(
+        &mut self,
+        prefix: &[u8],
+        payload: &[u8],
+    ) -> Result<(), FramesConsumerError<C::In>> {
+        let mut written = 0;
+        for mut bytes in [prefix, payload] {
+            while !bytes.is_empty() {
+                match self.consumer.consume_many(bytes) {
+                    Ok(amount) => {
+                        written += amount.get();
+                        bytes = &bytes[amount.get()..];
+                    }
+                    Err(inner) => return Err(FramesConsumerError::Inner { written, inner }),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn flush_inner(&mut self) -> Result<(), FramesConsumerError<C::In>> {
+        self.consumer
+            .flush()
+            .map_err(|inner| FramesConsumerError::Inner { written: 0, inner })
+    }
+
+    fn close_inner(&mut self, reason: C::Ex) -> Result<(), FramesConsumerError<C::In>> {
+        self.consumer
+            .close(reason)
+            .map_err(|inner| FramesConsumerError::Inner { written: 0, inner })
+    }
+}
+
+impl<'a, C: SizedConsumer<Item = u8>> Consumer for FramesConsumer<'a, C> {
+    type Item = &'a [u8];
+    type Ex = C::Ex;
+    type In = FramesConsumerError<C::In>;
+
+    fn consume(&mut self, item: &'a [u8]) -> Result<(), Self::In> {
+        let (prefix, prefix_len) = self.encode_prefix(item.len())?;
+        let needed = prefix_len.saturating_add(item.len());
+        if self.consumer.capacity_hint().0 < needed {
+            return Err(FramesConsumerError::InsufficientCapacity { needed });
+        }
+        self.write_frame(&prefix[..prefix_len], item)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::In> {
+        self.flush_inner()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), Self::In> {
+        self.close_inner(reason)
+    }
+}
+
+impl<'a, C: Consumer<Item = u8>> Consumer for FramesConsumer<'a, C, true> {
+    type Item = &'a [u8];
+    type Ex = C::Ex;
+    type In = FramesConsumerError<C::In>;
+
+    fn consume(&mut self, item: &'a [u8]) -> Result<(), Self::In> {
+        let (prefix, prefix_len) = self.encode_prefix(item.len())?;
+        self.write_frame(&prefix[..prefix_len], item)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::In> {
+        self.flush_inner()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), Self::In> {
+        self.close_inner(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::consumer::{ArrayConsumer, ArrayFull, SliceConsumer};
+    use crate::producer::{Endianness, FramesError, FramesProducer, SliceEnd, SliceProducer};
+
+    const PREFIXES: [LengthPrefix; 5] = [
+        LengthPrefix::U8,
+        LengthPrefix::U16(Endianness::Little),
+        LengthPrefix::U32(Endianness::Big),
+        LengthPrefix::U64(Endianness::Little),
+        LengthPrefix::Varint,
+    ];
+
+    #[test]
+    fn round_trip() {
+        let long = [7; 200];
+        let payloads: [&[u8]; 4] = [b"", b"abc", &long, b"\x00\xFF"];
+
+        for prefix in PREFIXES {
+            let mut buf = [0; 512];
+            let mut consumer = FramesConsumer::new(SliceConsumer::new(&mut buf), prefix);
+            for payload in payloads {
+                consumer.consume(payload).unwrap();
+            }
+            let written = consumer.into_inner().into_filled();
+
+            let mut producer = FramesProducer::new(SliceProducer::new(written), prefix);
+            let mut frame = [0; 256];
+            for payload in payloads {
+                let len = producer.produce_into(&mut frame).unwrap();
+                assert_eq!(&frame[..len], payload);
+            }
+            assert_eq!(
+                producer.produce_into(&mut frame),
+                Err(FramesError::Inner(SliceEnd))
+            );
+        }
+    }
+
+    #[test]
+    fn u32_prefix_is_big_endian() {
+        let mut consumer = FramesConsumer::new(
+            ArrayConsumer::<u8, 6>::new(),
+            LengthPrefix::U32(Endianness::Big),
+        );
+        consumer.consume(b"hi").unwrap();
+        assert_eq!(
+            consumer.into_inner().finish().unwrap(),
+            [0, 0, 0, 2, b'h', b'i']
+        );
+    }
+
+    #[test]
+    fn varint_prefix_matches_leb128() {
+        let mut consumer =
+            FramesConsumer::new(ArrayConsumer::<u8, 302>::new(), LengthPrefix::Varint);
+        consumer.consume(&[1; 300]).unwrap();
+        assert_eq!(&consumer.into_inner().as_slice()[..2], [0xAC, 0x02]);
+    }
+
+    #[test]
+    fn too_long() {
+        let mut consumer = FramesConsumer::new(ArrayConsumer::<u8, 512>::new(), LengthPrefix::U8);
+        assert_eq!(
+            consumer.consume(&[0; 256]),
+            Err(FramesConsumerError::TooLong { len: 256 })
+        );
+        assert!(consumer.into_inner().is_empty());
+    }
+
+    #[test]
+    fn insufficient_capacity_writes_nothing() {
+        let mut consumer = FramesConsumer::new(ArrayConsumer::<u8, 6>::new(), LengthPrefix::U8);
+        consumer.consume(b"abc").unwrap();
+        assert_eq!(
+            consumer.consume(b"de"),
+            Err(FramesConsumerError::InsufficientCapacity { needed: 3 })
+        );
+        consumer.consume(b"d").unwrap();
+        assert_eq!(
+            consumer.into_inner().finish().unwrap(),
+            [3, b'a', b'b', b'c', 1, b'd']
+        );
+    }
+
+    #[test]
+    fn partial_frames_report_written_bytes() {
+        let mut consumer = FramesConsumer::allowing_partial_frames(
+            ArrayConsumer::<u8, 3>::new(),
+            LengthPrefix::U8,
+        );
+        assert_eq!(
+            consumer.consume(b"abc"),
+            Err(FramesConsumerError::Inner {
+                written: 3,
+                inner: ArrayFull(b'c')
+            })
+        );
+        assert_eq!(consumer.into_inner().finish().unwrap(), [3, b'a', b'b']);
+    }
+}
@@ -0,0 +1,113 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::consumer::Consumer;
+
+/// Consumes items by calling a closure on them, which decides when an internal state change
+/// happens.
+///
+/// `flush` and `close` call further closures, which do nothing unless set with
+/// [`with_flush`](FromFnConsumer::with_flush) and [`with_close`](FromFnConsumer::with_close).
+/// After `close`, the closure for consuming items has been dropped and calling `consume` panics.
+pub struct FromFnConsumer<T, F, G, H, Ex = ()> {
+    consume: Option<F>,
+    flush: G,
+    close: H,
+    _item: PhantomData<fn(T, Ex)>,
+}
+
+/// The type of the default closure for `flush`.
+pub type NoFlush<In> = fn() -> Result<(), In>;
+
+/// The type of the default closure for `close`.
+pub type NoClose<In> = fn(()) -> Result<(), In>;
+
+/// Creates a consumer whose `consume` method calls the given closure.
+pub fn from_fn<T, In, F>(f: F) -> FromFnConsumer<T, F, NoFlush<In>, NoClose<In>>
+where
+    F: FnMut(T) -> Result<(), In>,
+{
+    FromFnConsumer::new(f, || Ok(()), |_| Ok(()))
+}
+
+impl<T, F, G, H, Ex> FromFnConsumer<T, F, G, H, Ex> {
+    pub fn new(consume: F, flush: G, close: H) -> Self {
+        FromFnConsumer {
+            consume: Some(consume),
+            flush,
+            close,
+            _item: PhantomData,
+        }
+    }
+
+    /// Sets the closure that `flush` calls.
+    pub fn with_flush<G2>(self, flush: G2) -> FromFnConsumer<T, F, G2, H, Ex> {
+        FromFnConsumer {
+            consume: self.consume,
+            flush,
+            close: self.close,
+            _item: PhantomData,
+        }
+    }
+
+    /// Sets the closure that `close` calls, which also determines the `Ex` type.
+    pub fn with_close<Ex2, H2>(self, close: H2) -> FromFnConsumer<T, F, G, H2, Ex2> {
+        FromFnConsumer {
+            consume: self.consume,
+            flush: self.flush,
+            close,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<T, In, Ex, F, G, H> Consumer for FromFnConsumer<T, F, G, H, Ex>
+where
+    F: FnMut(T) -> Result<(), In>,
+    G: FnMut() -> Result<(), In>,
+    H: FnMut(Ex) -> Result<(), In>,
+{
+    type Item = T;
+    type Ex = Ex;
+    type In = In;
+
+    fn consume(&mut self, item: T) -> Result<(), In> {
+        let f = self
+            .consume
+            .as_mut()
+            .expect("called consume on a closed FromFnConsumer");
+        f(item)
+    }
+
+    fn flush(&mut self) -> Result<(), In> {
+        (self.flush)()
+    }
+
+    fn close(&mut self, reason: Ex) -> Result<(), In> {
+        self.consume = None;
+        (self.close)(reason)
+    }
+}
+
+impl<T, F: Clone, G: Clone, H: Clone, Ex> Clone for FromFnConsumer<T, F, G, H, Ex> {
+    fn clone(&self) -> Self {
+        FromFnConsumer {
+            consume: self.consume.clone(),
+            flush: self.flush.clone(),
+            close: self.close.clone(),
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<T, F: fmt::Debug, G: fmt::Debug, H: fmt::Debug, Ex> fmt::Debug
+    for FromFnConsumer<T, F, G, H, Ex>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromFnConsumer")
+            .field("consume", &self.consume)
+            .field("flush", &self.flush)
+            .field("close", &self.close)
+            .finish()
+    }
+}
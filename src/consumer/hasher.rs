@@ -0,0 +1,151 @@
+use core::cmp;
+use core::convert::Infallible;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::num::NonZeroUsize;
+
+use crate::consumer::Consumer;
+
+const BUFFER_SIZE: usize = 64;
+
+/// Consumes bytes by feeding them to a [`core::hash::Hasher`], passing them on in chunks of up to
+/// 64 bytes via [`Hasher::write`].
+///
+/// Chunks end every 64 bytes and on `flush` and `close`, so for hashers whose result depends on
+/// how the input is split across calls to `write`, the hash also depends on when the consumer is
+/// flushed. Never performs an internal state change.
+#[derive(Debug, Clone)]
+pub struct HasherConsumer<H> {
+    hasher: H,
+    buf: [u8; BUFFER_SIZE],
+    len: usize,
+}
+
+impl<H: Hasher> HasherConsumer<H> {
+    pub fn new(hasher: H) -> Self {
+        HasherConsumer {
+            hasher,
+            buf: [0; BUFFER_SIZE],
+            len: 0,
+        }
+    }
+
+    /// Feeds the buffered bytes to the hasher and returns the hash of all consumed bytes.
+    pub fn finish(&mut self) -> u64 {
+        self.write_buffer();
+        self.hasher.finish()
+    }
+
+    /// Feeds the buffered bytes to the hasher and returns it.
+    pub fn into_inner(mut self) -> H {
+        self.write_buffer();
+        self.hasher
+    }
+
+    fn write_buffer(&mut self) {
+        if self.len > 0 {
+            self.hasher.write(&self.buf[..self.len]);
+            self.len = 0;
+        }
+    }
+}
+
+impl<H: Hasher> Consumer for HasherConsumer<H> {
+    type Item = u8;
+    type Ex = ();
+    type In = Infallible;
+
+    fn consume(&mut self, item: u8) -> Result<(), Infallible> {
+        self.buf[self.len] = item;
+        self.len += 1;
+        if self.len == BUFFER_SIZE {
+            self.write_buffer();
+        }
+        Ok(())
+    }
+
+    /// Copies as many bytes as fit into the buffer.
+    fn consume_many(&mut self, items: &[u8]) -> Result<NonZeroUsize, Infallible> {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = cmp::min(items.len(), BUFFER_SIZE - self.len);
+        self.buf[self.len..self.len + amount].copy_from_slice(&items[..amount]);
+        self.len += amount;
+        if self.len == BUFFER_SIZE {
+            self.write_buffer();
+        }
+        Ok(unsafe { NonZeroUsize::new_unchecked(amount) })
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        self.write_buffer();
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Infallible> {
+        self.write_buffer();
+        Ok(())
+    }
+}
+
+/// Consumes items by hashing them into a [`core::hash::Hasher`] with [`Hash::hash`].
+///
+/// Never performs an internal state change, `flush` and `close` do not do anything.
+pub struct HashConsumer<H, T> {
+    hasher: H,
+    _item: PhantomData<fn(T)>,
+}
+
+impl<H: Hasher, T> HashConsumer<H, T> {
+    pub fn new(hasher: H) -> Self {
+        HashConsumer {
+            hasher,
+            _item: PhantomData,
+        }
+    }
+
+    /// Returns the hash of all consumed items.
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+}
+
+impl<H: Hasher, T: Hash> Consumer for HashConsumer<H, T> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn consume(&mut self, item: T) -> Result<(), Infallible> {
+        item.hash(&mut self.hasher);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+impl<H: Clone, T> Clone for HashConsumer<H, T> {
+    fn clone(&self) -> Self {
+        HashConsumer {
+            hasher: self.hasher.clone(),
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<H: fmt::Debug, T> fmt::Debug for HashConsumer<H, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashConsumer")
+            .field("hasher", &self.hasher)
+            .finish()
+    }
+}
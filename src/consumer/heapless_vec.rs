@@ -0,0 +1,185 @@
+use core::cmp;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use heapless::Vec;
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1, SizedConsumer};
+
+/// The internal state change of a [`HeaplessVecConsumer`] whose vector is full, carrying the item
+/// that could not be consumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeaplessVecFull<T>(pub T);
+
+/// Consumes items by pushing them onto a [`heapless::Vec`].
+///
+/// Once the vector is full, `consume` returns the item in `Err(HeaplessVecFull(item))`. `flush`
+/// and `close` do not do anything, the vector can be retrieved with
+/// [`into_inner`](HeaplessVecConsumer::into_inner).
+#[derive(Debug, Clone)]
+pub struct HeaplessVecConsumer<T, const N: usize> {
+    vec: Vec<T, N>,
+}
+
+impl<T, const N: usize> HeaplessVecConsumer<T, N> {
+    pub fn new() -> Self {
+        HeaplessVecConsumer::from_vec(Vec::new())
+    }
+
+    /// Creates a consumer that pushes onto the given vector.
+    pub fn from_vec(vec: Vec<T, N>) -> Self {
+        HeaplessVecConsumer { vec }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.vec
+    }
+
+    /// Returns how many more items can be consumed.
+    pub fn remaining(&self) -> usize {
+        N - self.vec.len()
+    }
+
+    pub fn into_inner(self) -> Vec<T, N> {
+        self.vec
+    }
+}
+
+impl<T, const N: usize> Default for HeaplessVecConsumer<T, N> {
+    fn default() -> Self {
+        HeaplessVecConsumer::new()
+    }
+}
+
+impl<T, const N: usize> Consumer for HeaplessVecConsumer<T, N> {
+    type Item = T;
+    type Ex = ();
+    type In = HeaplessVecFull<T>;
+
+    fn consume(&mut self, item: T) -> Result<(), HeaplessVecFull<T>> {
+        self.vec.push(item).map_err(HeaplessVecFull)
+    }
+
+    /// Copies as many items as fit into the vector. If none fit, the first item is returned in the
+    /// internal state change.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, HeaplessVecFull<T>>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = cmp::min(items.len(), self.remaining());
+        if amount == 0 {
+            return Err(HeaplessVecFull(items[0]));
+        }
+
+        let _ = self.vec.extend_from_slice(&items[..amount]);
+        Ok(unsafe { NonZeroUsize::new_unchecked(amount) })
+    }
+
+    fn flush(&mut self) -> Result<(), HeaplessVecFull<T>> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), HeaplessVecFull<T>> {
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> SizedConsumer for HeaplessVecConsumer<T, N> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+/// Moves as many items of the loaf as fit into the vector. If none fit, the first item is
+/// returned in the internal state change.
+impl<T, const N: usize> ConsumerFromMany1 for HeaplessVecConsumer<T, N> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<T>,
+    ) -> Result<NonZeroUsize, HeaplessVecFull<T>> {
+        let amount = cmp::min((*items).len(), self.remaining());
+        if amount == 0 {
+            return Err(HeaplessVecFull(ptr::read(items as *const T)));
+        }
+
+        let end = self.vec.as_mut_ptr().add(self.vec.len());
+        ptr::copy_nonoverlapping(items as *const T, end, amount);
+        self.vec.set_len(self.vec.len() + amount);
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+    use core::mem::ManuallyDrop;
+
+    use crate::testing::DropCounted;
+
+    // Moves the items of `items` into the consumer with `consume_from_many1`, then drops the items
+    // that have not been read. Returns what `consume_from_many1` returned.
+    fn move_from<'a, const N: usize, const M: usize>(
+        consumer: &mut HeaplessVecConsumer<DropCounted<'a, usize>, N>,
+        items: [DropCounted<'a, usize>; M],
+    ) -> Result<NonZeroUsize, HeaplessVecFull<DropCounted<'a, usize>>> {
+        let mut items = ManuallyDrop::new(items);
+        let loaf = ptr::slice_from_raw_parts(items.as_ptr(), M - 1) as *const Loaf<_>;
+        let result = unsafe { consumer.consume_from_many1(loaf) };
+        let read = match &result {
+            Ok(amount) => amount.get(),
+            Err(_) => 1,
+        };
+        unsafe { ptr::drop_in_place(&mut items[read..]) };
+        result
+    }
+
+    #[test]
+    fn rejected_item_is_returned() {
+        let drops = Cell::new(0);
+        let mut consumer = HeaplessVecConsumer::<_, 1>::new();
+        assert_eq!(consumer.consume(DropCounted::new(1, &drops)), Ok(()));
+
+        let rejected = consumer.consume(DropCounted::new(2, &drops)).unwrap_err();
+        assert_eq!(rejected.0.value, 2);
+        assert_eq!(drops.get(), 0);
+        drop(rejected);
+        assert_eq!(drops.get(), 1);
+        assert_eq!(consumer.as_slice()[0].value, 1);
+    }
+
+    #[test]
+    fn consume_many_caps_at_remaining() {
+        let mut consumer = HeaplessVecConsumer::<u8, 3>::new();
+        assert_eq!(
+            consumer.consume_many(&[1, 2, 3, 4]).map(NonZeroUsize::get),
+            Ok(3)
+        );
+        assert_eq!(consumer.consume_many(&[5, 6]), Err(HeaplessVecFull(5)));
+        assert_eq!(consumer.as_slice(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn consume_from_many1_moves_what_fits() {
+        let drops = Cell::new(0);
+        let mut consumer = HeaplessVecConsumer::<_, 3>::new();
+        let items = [0, 1, 2, 3].map(|i| DropCounted::new(i, &drops));
+        assert_eq!(
+            move_from(&mut consumer, items).map(NonZeroUsize::get),
+            Ok(3)
+        );
+        // Only the item that did not fit has been dropped.
+        assert_eq!(drops.get(), 1);
+
+        let rejected = move_from(&mut consumer, [DropCounted::new(4, &drops)]).unwrap_err();
+        assert_eq!(rejected.0.value, 4);
+        drop(rejected);
+        assert_eq!(drops.get(), 2);
+
+        drop(consumer);
+        assert_eq!(drops.get(), 5);
+    }
+}
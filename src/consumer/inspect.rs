@@ -0,0 +1,68 @@
+use crate::consumer::Consumer;
+
+/// Calls a function with a reference to every item before passing it to the wrapped consumer.
+///
+/// The function is also called for an item whose consumption then results in an internal state
+/// change, since the item is moved into the wrapped consumer.
+#[derive(Debug, Clone)]
+pub struct InspectConsumer<C, F> {
+    consumer: C,
+    f: F,
+}
+
+impl<C, F> InspectConsumer<C, F> {
+    pub fn new(consumer: C, f: F) -> Self {
+        InspectConsumer { consumer, f }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.consumer
+    }
+}
+
+impl<C: Consumer, F: FnMut(&C::Item)> Consumer for InspectConsumer<C, F> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: C::Item) -> Result<(), C::In> {
+        (self.f)(&item);
+        self.consumer.consume(item)
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        self.consumer.flush()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        self.consumer.close(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ConsumerResponse, ScriptedConsumer};
+
+    #[test]
+    fn called_once_per_consumed_item() {
+        let mut seen = 0;
+        let inner = ScriptedConsumer::<u8, (), ()>::new([
+            ConsumerResponse::Accept,
+            ConsumerResponse::Accept,
+            ConsumerResponse::Accept,
+            ConsumerResponse::StateChange(()),
+        ]);
+        let mut consumer = InspectConsumer::new(inner, |_: &u8| seen += 1);
+
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.flush(), Ok(()));
+        assert_eq!(consumer.consume(2), Ok(()));
+        assert_eq!(consumer.consume(3), Err(()));
+        drop(consumer);
+
+        // The item that caused the state change is inspected as well.
+        assert_eq!(seen, 3);
+    }
+}
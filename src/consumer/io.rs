@@ -0,0 +1,121 @@
+use core::cmp;
+use core::num::NonZeroUsize;
+
+use std::boxed::Box;
+use std::io::{self, ErrorKind, Write};
+use std::vec;
+
+use crate::consumer::Consumer;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Consumes bytes by writing them to a [`std::io::Write`], buffering them internally.
+///
+/// The buffer is written out when it is full, on `flush` (which also flushes the writer) and on
+/// `close`. Partial and interrupted writes are retried until the whole buffer has been written, a
+/// write of zero bytes results in an error of kind `WriteZero`. If writing fails, the bytes that
+/// have not been written stay buffered, so the operation can be retried.
+#[derive(Debug)]
+pub struct IoWriteConsumer<W> {
+    writer: W,
+    buf: Box<[u8]>,
+    filled: usize,
+}
+
+impl<W: Write> IoWriteConsumer<W> {
+    pub fn new(writer: W) -> Self {
+        IoWriteConsumer::with_capacity(DEFAULT_CAPACITY, writer)
+    }
+
+    /// Creates a consumer whose buffer holds `capacity` bytes.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        assert!(capacity > 0, "IoWriteConsumer requires a nonzero capacity");
+        IoWriteConsumer {
+            writer,
+            buf: vec![0; capacity].into_boxed_slice(),
+            filled: 0,
+        }
+    }
+
+    /// Returns the bytes that have been consumed but not written yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the writer. Bytes that have not been written yet are lost, call `flush` or `close`
+    /// first to write them.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    // Writes out the whole buffer.
+    fn write_buffer(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.filled {
+                break Ok(());
+            }
+
+            match self.writer.write(&self.buf[written..self.filled]) {
+                Ok(0) => {
+                    break Err(io::Error::new(
+                        ErrorKind::WriteZero,
+                        "failed to write the buffered bytes",
+                    ))
+                }
+                Ok(n) => written += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+
+        self.buf.copy_within(written..self.filled, 0);
+        self.filled -= written;
+        result
+    }
+}
+
+impl<W: Write> Consumer for IoWriteConsumer<W> {
+    type Item = u8;
+    type Ex = ();
+    type In = io::Error;
+
+    fn consume(&mut self, item: u8) -> Result<(), io::Error> {
+        if self.filled == self.buf.len() {
+            self.write_buffer()?;
+        }
+
+        self.buf[self.filled] = item;
+        self.filled += 1;
+        Ok(())
+    }
+
+    /// Copies as many bytes as fit into the buffer, writing it out first if it is full.
+    fn consume_many(&mut self, items: &[u8]) -> Result<NonZeroUsize, io::Error> {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        if self.filled == self.buf.len() {
+            self.write_buffer()?;
+        }
+
+        let amount = cmp::min(items.len(), self.buf.len() - self.filled);
+        self.buf[self.filled..self.filled + amount].copy_from_slice(&items[..amount]);
+        self.filled += amount;
+        Ok(unsafe { NonZeroUsize::new_unchecked(amount) })
+    }
+
+    fn flush(&mut self) -> Result<(), io::Error> {
+        self.write_buffer()?;
+        self.writer.flush()
+    }
+
+    /// Flushes, the writer can then be retrieved with `into_inner`.
+    fn close(&mut self, _reason: ()) -> Result<(), io::Error> {
+        self.flush()
+    }
+}
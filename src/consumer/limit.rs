@@ -0,0 +1,108 @@
+use core::cmp;
+
+use crate::consumer::{Consumer, SizedConsumer};
+use crate::Either;
+
+/// The internal state change of a [`LimitConsumer`] that has already consumed as many items as it
+/// may.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LimitReached;
+
+/// Passes at most a fixed number of items to the wrapped consumer.
+///
+/// Once the limit has been reached, `consume` drops the item and returns
+/// `Err(Either::Right(LimitReached))`. Items whose consumption by the wrapped consumer fails do not
+/// count toward the limit. `flush` and `close` are always passed on.
+#[derive(Debug, Clone)]
+pub struct LimitConsumer<C> {
+    consumer: C,
+    remaining: usize,
+}
+
+impl<C> LimitConsumer<C> {
+    pub fn new(consumer: C, n: usize) -> Self {
+        LimitConsumer {
+            consumer,
+            remaining: n,
+        }
+    }
+
+    /// Returns how many more items will be passed on.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    pub fn into_inner(self) -> C {
+        self.consumer
+    }
+}
+
+impl<C: Consumer> Consumer for LimitConsumer<C> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = Either<C::In, LimitReached>;
+
+    fn consume(&mut self, item: C::Item) -> Result<(), Self::In> {
+        if self.remaining == 0 {
+            return Err(Either::Right(LimitReached));
+        }
+
+        self.consumer.consume(item).map_err(Either::Left)?;
+        self.remaining -= 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::In> {
+        self.consumer.flush().map_err(Either::Left)
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), Self::In> {
+        self.consumer.close(reason).map_err(Either::Left)
+    }
+}
+
+impl<C: SizedConsumer> SizedConsumer for LimitConsumer<C> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.consumer.capacity_hint();
+        let upper = match upper {
+            Some(upper) => cmp::min(upper, self.remaining),
+            None => self.remaining,
+        };
+        (cmp::min(lower, self.remaining), Some(upper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::consumer::{ArrayConsumer, ArrayFull, SizedConsumer};
+
+    #[test]
+    fn passes_exactly_n_items() {
+        let mut consumer = LimitConsumer::new(ArrayConsumer::<u8, 8>::new(), 3);
+        for item in 0..3 {
+            assert_eq!(consumer.consume(item), Ok(()));
+        }
+        assert_eq!(consumer.remaining(), 0);
+        assert_eq!(consumer.consume(3), Err(Either::Right(LimitReached)));
+        assert_eq!(consumer.consume(4), Err(Either::Right(LimitReached)));
+        assert_eq!(consumer.into_inner().as_slice(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn failed_items_do_not_count() {
+        let mut consumer = LimitConsumer::new(ArrayConsumer::<u8, 1>::new(), 2);
+        assert_eq!(consumer.consume(0), Ok(()));
+        assert_eq!(consumer.consume(1), Err(Either::Left(ArrayFull(1))));
+        assert_eq!(consumer.remaining(), 1);
+    }
+
+    #[test]
+    fn capacity_hint() {
+        let consumer = LimitConsumer::new(ArrayConsumer::<u8, 8>::new(), 3);
+        assert_eq!(consumer.capacity_hint(), (3, Some(3)));
+        let consumer = LimitConsumer::new(ArrayConsumer::<u8, 2>::new(), 3);
+        assert_eq!(consumer.capacity_hint(), (2, Some(2)));
+    }
+}
@@ -0,0 +1,61 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::consumer::Consumer;
+
+/// Applies a function to every item before passing it to the wrapped consumer.
+///
+/// Unlike [`MapProducer`](crate::producer::MapProducer), the input type `B` of the function has to
+/// be a type parameter, since nothing else determines it.
+pub struct MapConsumer<C, F, B> {
+    consumer: C,
+    f: F,
+    _item: PhantomData<fn(B)>,
+}
+
+impl<C, F, B> MapConsumer<C, F, B> {
+    pub fn new(consumer: C, f: F) -> Self {
+        MapConsumer {
+            consumer,
+            f,
+            _item: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.consumer
+    }
+}
+
+impl<B, C: Consumer, F: FnMut(B) -> C::Item> Consumer for MapConsumer<C, F, B> {
+    type Item = B;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: B) -> Result<(), C::In> {
+        self.consumer.consume((self.f)(item))
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        self.consumer.flush()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        self.consumer.close(reason)
+    }
+}
+
+impl<C: Clone, F: Clone, B> Clone for MapConsumer<C, F, B> {
+    fn clone(&self) -> Self {
+        MapConsumer::new(self.consumer.clone(), self.f.clone())
+    }
+}
+
+impl<C: fmt::Debug, F: fmt::Debug, B> fmt::Debug for MapConsumer<C, F, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapConsumer")
+            .field("consumer", &self.consumer)
+            .field("f", &self.f)
+            .finish()
+    }
+}
@@ -0,0 +1,56 @@
+use core::num::NonZeroUsize;
+
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1};
+
+/// Applies a function to every internal state change of the wrapped consumer.
+#[derive(Debug, Clone)]
+pub struct MapErrConsumer<C, F> {
+    consumer: C,
+    f: F,
+}
+
+impl<C, F> MapErrConsumer<C, F> {
+    pub fn new(consumer: C, f: F) -> Self {
+        MapErrConsumer { consumer, f }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.consumer
+    }
+}
+
+impl<E, C: Consumer, F: FnMut(C::In) -> E> Consumer for MapErrConsumer<C, F> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = E;
+
+    fn consume(&mut self, item: C::Item) -> Result<(), E> {
+        self.consumer.consume(item).map_err(&mut self.f)
+    }
+
+    fn consume_many(&mut self, items: &[C::Item]) -> Result<NonZeroUsize, E>
+    where
+        C::Item: Copy,
+    {
+        self.consumer.consume_many(items).map_err(&mut self.f)
+    }
+
+    fn flush(&mut self) -> Result<(), E> {
+        self.consumer.flush().map_err(&mut self.f)
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), E> {
+        self.consumer.close(reason).map_err(&mut self.f)
+    }
+}
+
+impl<E, C: ConsumerFromMany1, F: FnMut(C::In) -> E> ConsumerFromMany1 for MapErrConsumer<C, F> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<C::Item>,
+    ) -> Result<NonZeroUsize, E> {
+        self.consumer.consume_from_many1(items).map_err(&mut self.f)
+    }
+}
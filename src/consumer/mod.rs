@@ -0,0 +1,250 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+mod array;
+mod buffered;
+mod checksum;
+mod counting;
+mod either;
+mod empty;
+mod ext;
+#[cfg(feature = "alloc")]
+mod extend;
+mod fmt;
+mod frames;
+mod from_fn;
+mod hasher;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
+mod inspect;
+#[cfg(feature = "std")]
+mod io;
+mod limit;
+mod map;
+mod map_err;
+#[cfg(feature = "std")]
+mod mpsc;
+mod partition;
+mod sink;
+mod slice;
+#[cfg(feature = "alloc")]
+mod string;
+mod tee;
+#[cfg(feature = "alloc")]
+mod vec;
+
+pub use array::{ArrayConsumer, ArrayFull};
+pub use buffered::BufferedConsumer;
+#[cfg(feature = "crc32")]
+pub use checksum::Crc32Hasher;
+pub use checksum::{ChecksumConsumer, XorHasher};
+pub use counting::{ConsumerCounts, CountingConsumer};
+pub use either::EitherConsumer;
+pub use empty::EmptyConsumer;
+pub use ext::ConsumerExt;
+#[cfg(feature = "alloc")]
+pub use extend::ExtendConsumer;
+pub use fmt::{FmtStrConsumer, FmtWriteConsumer};
+pub use frames::{FramesConsumer, FramesConsumerError};
+pub use from_fn::{from_fn, FromFnConsumer, NoClose, NoFlush};
+pub use hasher::{HashConsumer, HasherConsumer};
+#[cfg(feature = "heapless")]
+pub use heapless_vec::{HeaplessVecConsumer, HeaplessVecFull};
+pub use inspect::InspectConsumer;
+#[cfg(feature = "std")]
+pub use io::IoWriteConsumer;
+pub use limit::{LimitConsumer, LimitReached};
+pub use map::MapConsumer;
+pub use map_err::MapErrConsumer;
+#[cfg(feature = "std")]
+pub use mpsc::{SenderConsumer, SyncSenderConsumer};
+pub use partition::PartitionConsumer;
+pub use sink::{counting_sink, sink, CountingSinkConsumer, SinkConsumer};
+pub use slice::{SliceConsumer, SliceFull, UninitSliceConsumer};
+#[cfg(feature = "alloc")]
+pub use string::{StringConsumer, Utf8StringConsumer, Utf8StringError};
+pub use tee::TeeConsumer;
+#[cfg(feature = "alloc")]
+pub use vec::VecConsumer;
+
+/// A sequence manipulator that moves items into a sequence, front to back.
+///
+/// `consume` corresponds to a `Write` followed by a `Next`, `flush` corresponds to `FlushPrev`,
+/// and `close` corresponds to `StopWrite`. As with all sequence manipulators, a method that
+/// returns an `Err` (an *internal state change*) must not have modified the sequence, and calling
+/// any method after an internal state change or after `close` results in unspecified behavior
+/// unless an implementation documents otherwise.
+pub trait Consumer {
+    /// The type of the items in the sequence.
+    type Item;
+    /// Information about why `close` is being called.
+    type Ex;
+    /// Information about an internal state change.
+    type In;
+
+    /// Moves an item into the sequence at the current position and advances the position.
+    fn consume(&mut self, item: Self::Item) -> Result<(), Self::In>;
+
+    /// Consumes copies of the items at the start of `items` until all of them have been consumed or
+    /// an internal state change happens, and returns how many items were consumed.
+    ///
+    /// Only performs an internal state change if not a single item could be consumed. An internal
+    /// state change that happens after consuming at least one item is discarded, calling a method
+    /// afterwards then follows the same rules as calling a method after that internal state change.
+    ///
+    /// Panics if `items` is empty.
+    fn consume_many(&mut self, items: &[Self::Item]) -> Result<NonZeroUsize, Self::In>
+    where
+        Self::Item: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        self.consume(items[0])?;
+
+        let mut count = 1;
+        for item in &items[1..] {
+            if self.consume(*item).is_err() {
+                break;
+            }
+            count += 1;
+        }
+        Ok(unsafe { NonZeroUsize::new_unchecked(count) })
+    }
+
+    /// Flushes all buffered items.
+    fn flush(&mut self) -> Result<(), Self::In>;
+
+    /// Notifies the consumer that `consume` will not be called anymore.
+    fn close(&mut self, reason: Self::Ex) -> Result<(), Self::In>;
+}
+
+/// A consumer that knows bounds on how many items it will accept before its next internal state
+/// change.
+pub trait SizedConsumer: Consumer {
+    /// Returns a lower bound and an optional upper bound on the number of items that can be
+    /// consumed before the next internal state change. The lower bound is a guarantee, consuming
+    /// that many items in a row must succeed.
+    fn capacity_hint(&self) -> (usize, Option<usize>);
+}
+
+/// A consumer that can move items into the sequence by reading them from a pointer.
+///
+/// Implemented for all consumers.
+pub trait ConsumerFrom: Consumer {
+    /// Reads the item behind the pointer and consumes it.
+    ///
+    /// # Safety
+    ///
+    /// `item` must be valid for a [`core::ptr::read`]. The item is read exactly once, regardless
+    /// of whether this returns `Ok` or `Err`, so the caller must treat it as moved out.
+    unsafe fn consume_from(&mut self, item: *const Self::Item) -> Result<(), Self::In> {
+        self.consume(ptr::read(item))
+    }
+}
+
+impl<C: Consumer + ?Sized> ConsumerFrom for C {}
+
+/// A consumer that can move multiple items into the sequence by reading them from a pointer.
+pub trait ConsumerFromMany1: ConsumerFrom {
+    /// Reads and consumes a nonzero number of items from the start of the loaf, returning how
+    /// many.
+    ///
+    /// # Safety
+    ///
+    /// All items of `items` must be valid for a [`core::ptr::read`]. When returning `Ok(n)`,
+    /// exactly the first `n` items have been read, when returning `Err` exactly the first item
+    /// has been read. The caller keeps ownership of all other items.
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<Self::Item>,
+    ) -> Result<NonZeroUsize, Self::In> {
+        self.consume_from(items as *const Self::Item)?;
+        Ok(NonZeroUsize::new_unchecked(1))
+    }
+}
+
+impl<C: Consumer + ?Sized> Consumer for &mut C {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: C::Item) -> Result<(), C::In> {
+        (**self).consume(item)
+    }
+
+    fn consume_many(&mut self, items: &[C::Item]) -> Result<NonZeroUsize, C::In>
+    where
+        C::Item: Copy,
+    {
+        (**self).consume_many(items)
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        (**self).flush()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        (**self).close(reason)
+    }
+}
+
+impl<C: SizedConsumer + ?Sized> SizedConsumer for &mut C {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (**self).capacity_hint()
+    }
+}
+
+impl<C: ConsumerFromMany1 + ?Sized> ConsumerFromMany1 for &mut C {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<C::Item>,
+    ) -> Result<NonZeroUsize, C::In> {
+        (**self).consume_from_many1(items)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: Consumer + ?Sized> Consumer for Box<C> {
+    type Item = C::Item;
+    type Ex = C::Ex;
+    type In = C::In;
+
+    fn consume(&mut self, item: C::Item) -> Result<(), C::In> {
+        (**self).consume(item)
+    }
+
+    fn consume_many(&mut self, items: &[C::Item]) -> Result<NonZeroUsize, C::In>
+    where
+        C::Item: Copy,
+    {
+        (**self).consume_many(items)
+    }
+
+    fn flush(&mut self) -> Result<(), C::In> {
+        (**self).flush()
+    }
+
+    fn close(&mut self, reason: C::Ex) -> Result<(), C::In> {
+        (**self).close(reason)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: SizedConsumer + ?Sized> SizedConsumer for Box<C> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (**self).capacity_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: ConsumerFromMany1 + ?Sized> ConsumerFromMany1 for Box<C> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<C::Item>,
+    ) -> Result<NonZeroUsize, C::In> {
+        (**self).consume_from_many1(items)
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::mpsc::{SendError, Sender, SyncSender};
+
+use crate::consumer::Consumer;
+
+/// Consumes items by sending them over an unbounded [`std::sync::mpsc`] channel.
+///
+/// If the receiver has disconnected, `consume` returns the item in an `Err(SendError)`. `flush`
+/// does nothing. `close` drops the sender, so a [`ReceiverProducer`] on the other end observes the
+/// end of the sequence once all other senders are gone, and `consume` afterwards hands back every
+/// item in an `Err(SendError)`.
+///
+/// [`ReceiverProducer`]: crate::producer::ReceiverProducer
+#[derive(Debug, Clone)]
+pub struct SenderConsumer<T> {
+    sender: Option<Sender<T>>,
+}
+
+impl<T> SenderConsumer<T> {
+    pub fn new(sender: Sender<T>) -> Self {
+        SenderConsumer {
+            sender: Some(sender),
+        }
+    }
+
+    /// Returns the sender, or `None` if `close` has been called.
+    pub fn into_inner(self) -> Option<Sender<T>> {
+        self.sender
+    }
+}
+
+impl<T> Consumer for SenderConsumer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = SendError<T>;
+
+    fn consume(&mut self, item: T) -> Result<(), SendError<T>> {
+        match self.sender.as_ref() {
+            Some(sender) => sender.send(item),
+            None => Err(SendError(item)),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), SendError<T>> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), SendError<T>> {
+        self.sender = None;
+        Ok(())
+    }
+}
+
+/// Consumes items by sending them over a bounded [`std::sync::mpsc`] channel, blocking while the
+/// channel is full.
+///
+/// Behaves like [`SenderConsumer`] otherwise.
+#[derive(Debug, Clone)]
+pub struct SyncSenderConsumer<T> {
+    sender: Option<SyncSender<T>>,
+}
+
+impl<T> SyncSenderConsumer<T> {
+    pub fn new(sender: SyncSender<T>) -> Self {
+        SyncSenderConsumer {
+            sender: Some(sender),
+        }
+    }
+
+    /// Returns the sender, or `None` if `close` has been called.
+    pub fn into_inner(self) -> Option<SyncSender<T>> {
+        self.sender
+    }
+}
+
+impl<T> Consumer for SyncSenderConsumer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = SendError<T>;
+
+    fn consume(&mut self, item: T) -> Result<(), SendError<T>> {
+        match self.sender.as_ref() {
+            Some(sender) => sender.send(item),
+            None => Err(SendError(item)),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), SendError<T>> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), SendError<T>> {
+        self.sender = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, sync_channel, RecvError};
+    use std::thread;
+    use std::vec::Vec;
+
+    use crate::consumer::VecConsumer;
+    use crate::pipe;
+    use crate::producer::{ReceiverProducer, SliceEnd, SliceProducer};
+    use crate::PipeError;
+
+    fn receive_all(producer: &mut ReceiverProducer<u32>) -> Vec<u32> {
+        let mut consumer = VecConsumer::new();
+        assert_eq!(
+            pipe(producer, &mut consumer),
+            Err(PipeError::Producer(RecvError))
+        );
+        consumer.into_vec()
+    }
+
+    #[test]
+    fn sender_to_receiver_across_threads() {
+        let input: Vec<u32> = (0..1000).collect();
+        let (sender, receiver) = channel();
+
+        let received = thread::scope(|s| {
+            s.spawn(|| {
+                let mut producer = SliceProducer::new(&input);
+                let mut consumer = SenderConsumer::new(sender);
+                assert_eq!(
+                    pipe(&mut producer, &mut consumer),
+                    Err(PipeError::Producer(SliceEnd))
+                );
+                assert!(consumer.close(()).is_ok());
+            });
+            s.spawn(|| receive_all(&mut ReceiverProducer::with_capacity(7, receiver)))
+                .join()
+                .unwrap()
+        });
+
+        assert_eq!(received, input);
+    }
+
+    #[test]
+    fn sync_sender_to_receiver_across_threads() {
+        let input: Vec<u32> = (0..1000).collect();
+        let (sender, receiver) = sync_channel(3);
+
+        let received = thread::scope(|s| {
+            s.spawn(|| {
+                let mut producer = SliceProducer::new(&input);
+                let mut consumer = SyncSenderConsumer::new(sender);
+                assert_eq!(
+                    pipe(&mut producer, &mut consumer),
+                    Err(PipeError::Producer(SliceEnd))
+                );
+                assert!(consumer.close(()).is_ok());
+            });
+            s.spawn(|| receive_all(&mut ReceiverProducer::new(receiver)))
+                .join()
+                .unwrap()
+        });
+
+        assert_eq!(received, input);
+    }
+
+    #[test]
+    fn consume_after_close_returns_item() {
+        let (sender, _receiver) = channel();
+        let mut consumer = SenderConsumer::new(sender);
+        assert!(consumer.close(()).is_ok());
+        assert_eq!(consumer.consume(1), Err(SendError(1)));
+        assert!(consumer.into_inner().is_none());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::consumer::Consumer;
+use crate::Either;
+
+/// Passes every item that satisfies a predicate to a first consumer, and all other items to a
+/// second consumer.
+///
+/// `flush` flushes both consumers, `close` closes both consumers, in both cases even if the
+/// first one fails.
+#[derive(Debug, Clone)]
+pub struct PartitionConsumer<A, B, F> {
+    a: A,
+    b: B,
+    predicate: F,
+}
+
+impl<A, B, F> PartitionConsumer<A, B, F> {
+    pub fn new(a: A, b: B, predicate: F) -> Self {
+        PartitionConsumer { a, b, predicate }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B, F> Consumer for PartitionConsumer<A, B, F>
+where
+    A: Consumer,
+    B: Consumer<Item = A::Item>,
+    F: FnMut(&A::Item) -> bool,
+{
+    type Item = A::Item;
+    type Ex = (A::Ex, B::Ex);
+    type In = Either<A::In, B::In>;
+
+    fn consume(&mut self, item: A::Item) -> Result<(), Self::In> {
+        if (self.predicate)(&item) {
+            self.a.consume(item).map_err(Either::Left)
+        } else {
+            self.b.consume(item).map_err(Either::Right)
+        }
+    }
+
+    /// Flushes both consumers, even if flushing the first one fails.
+    fn flush(&mut self) -> Result<(), Self::In> {
+        let a = self.a.flush().map_err(Either::Left);
+        let b = self.b.flush().map_err(Either::Right);
+        a.and(b)
+    }
+
+    /// Closes both consumers, even if closing the first one fails.
+    fn close(&mut self, (reason_a, reason_b): Self::Ex) -> Result<(), Self::In> {
+        let a = self.a.close(reason_a).map_err(Either::Left);
+        let b = self.b.close(reason_b).map_err(Either::Right);
+        a.and(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ConsumerCall, ConsumerResponse, ScriptedConsumer};
+
+    #[test]
+    fn routes_items_by_predicate() {
+        let mut consumer = PartitionConsumer::new(
+            ScriptedConsumer::<u8, (), ()>::new([]),
+            ScriptedConsumer::<u8, (), ()>::new([]),
+            |n: &u8| n.is_multiple_of(2),
+        );
+        for n in 0..4 {
+            consumer.consume(n).unwrap();
+        }
+        consumer.close(((), ())).unwrap();
+
+        let (evens, odds) = consumer.into_inner();
+        assert_eq!(
+            evens.log(),
+            [
+                ConsumerCall::Consume(0),
+                ConsumerCall::Consume(2),
+                ConsumerCall::Close(())
+            ]
+        );
+        assert_eq!(
+            odds.log(),
+            [
+                ConsumerCall::Consume(1),
+                ConsumerCall::Consume(3),
+                ConsumerCall::Close(())
+            ]
+        );
+    }
+
+    #[test]
+    fn flush_reaches_second_consumer_if_first_fails() {
+        let mut consumer = PartitionConsumer::new(
+            ScriptedConsumer::<u8, &str, ()>::new([ConsumerResponse::StateChange("a")]),
+            ScriptedConsumer::<u8, &str, ()>::new([]),
+            |_: &u8| true,
+        );
+        assert_eq!(consumer.flush(), Err(Either::Left("a")));
+
+        let (a, b) = consumer.into_inner();
+        assert_eq!(a.log(), [ConsumerCall::Flush]);
+        assert_eq!(b.log(), [ConsumerCall::Flush]);
+    }
+}
@@ -0,0 +1,173 @@
+use core::convert::Infallible;
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1};
+
+/// Consumes items by dropping them, never performing an internal state change.
+///
+/// The `In` type is only a parameter so that the consumer can be used where some specific type of
+/// internal state change is expected.
+pub struct SinkConsumer<T, In = Infallible> {
+    _item: PhantomData<fn(T) -> In>,
+}
+
+impl<T, In> SinkConsumer<T, In> {
+    pub fn new() -> Self {
+        SinkConsumer { _item: PhantomData }
+    }
+}
+
+/// Creates a consumer that drops all items.
+pub fn sink<T>() -> SinkConsumer<T> {
+    SinkConsumer::new()
+}
+
+impl<T, In> Consumer for SinkConsumer<T, In> {
+    type Item = T;
+    type Ex = ();
+    type In = In;
+
+    fn consume(&mut self, _item: T) -> Result<(), In> {
+        Ok(())
+    }
+
+    /// Always consumes all items.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, In>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        Ok(unsafe { NonZeroUsize::new_unchecked(items.len()) })
+    }
+
+    fn flush(&mut self) -> Result<(), In> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), In> {
+        Ok(())
+    }
+}
+
+/// Always consumes all items of the loaf.
+impl<T, In> ConsumerFromMany1 for SinkConsumer<T, In> {
+    unsafe fn consume_from_many1(&mut self, items: *const Loaf<T>) -> Result<NonZeroUsize, In> {
+        let amount = (*items).len();
+        ptr::drop_in_place(ptr::slice_from_raw_parts_mut(items as *mut T, amount));
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+impl<T, In> Default for SinkConsumer<T, In> {
+    fn default() -> Self {
+        SinkConsumer::new()
+    }
+}
+
+impl<T, In> Clone for SinkConsumer<T, In> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, In> Copy for SinkConsumer<T, In> {}
+
+impl<T, In> fmt::Debug for SinkConsumer<T, In> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SinkConsumer")
+    }
+}
+
+/// Consumes items by dropping them and counting how many there were.
+pub struct CountingSinkConsumer<T> {
+    count: usize,
+    _item: PhantomData<fn(T)>,
+}
+
+impl<T> CountingSinkConsumer<T> {
+    pub fn new() -> Self {
+        CountingSinkConsumer {
+            count: 0,
+            _item: PhantomData,
+        }
+    }
+
+    /// Returns how many items have been consumed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Creates a consumer that drops all items and counts them.
+pub fn counting_sink<T>() -> CountingSinkConsumer<T> {
+    CountingSinkConsumer::new()
+}
+
+impl<T> Consumer for CountingSinkConsumer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn consume(&mut self, _item: T) -> Result<(), Infallible> {
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Always consumes all items.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, Infallible>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        self.count += items.len();
+        Ok(unsafe { NonZeroUsize::new_unchecked(items.len()) })
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// Always consumes all items of the loaf.
+impl<T> ConsumerFromMany1 for CountingSinkConsumer<T> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<T>,
+    ) -> Result<NonZeroUsize, Infallible> {
+        let amount = (*items).len();
+        ptr::drop_in_place(ptr::slice_from_raw_parts_mut(items as *mut T, amount));
+        self.count += amount;
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+impl<T> Default for CountingSinkConsumer<T> {
+    fn default() -> Self {
+        CountingSinkConsumer::new()
+    }
+}
+
+impl<T> Clone for CountingSinkConsumer<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CountingSinkConsumer<T> {}
+
+impl<T> fmt::Debug for CountingSinkConsumer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountingSinkConsumer")
+            .field("count", &self.count)
+            .finish()
+    }
+}
@@ -0,0 +1,313 @@
+use core::cmp;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1, SizedConsumer};
+
+/// The internal state change of a [`SliceConsumer`] whose slice is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SliceFull;
+
+/// Consumes items by writing them into a mutable slice, front to back, dropping the values they
+/// overwrite.
+///
+/// Once the slice is full, `consume` drops the item and returns `Err(SliceFull)`. `flush` and
+/// `close` do not do anything.
+#[derive(Debug)]
+pub struct SliceConsumer<'a, T> {
+    slice: &'a mut [T],
+    filled: usize,
+}
+
+impl<'a, T> SliceConsumer<'a, T> {
+    pub fn new(slice: &'a mut [T]) -> Self {
+        SliceConsumer { slice, filled: 0 }
+    }
+
+    /// Returns how many items have been written.
+    pub fn filled(&self) -> usize {
+        self.filled
+    }
+
+    /// Returns how many more items can be written.
+    pub fn remaining(&self) -> usize {
+        self.slice.len() - self.filled
+    }
+
+    /// Returns the part of the slice that has been written to.
+    pub fn into_filled(self) -> &'a mut [T] {
+        &mut self.slice[..self.filled]
+    }
+}
+
+impl<'a, T> Consumer for SliceConsumer<'a, T> {
+    type Item = T;
+    type Ex = ();
+    type In = SliceFull;
+
+    fn consume(&mut self, item: T) -> Result<(), SliceFull> {
+        let slot = self.slice.get_mut(self.filled).ok_or(SliceFull)?;
+        *slot = item;
+        self.filled += 1;
+        Ok(())
+    }
+
+    /// Copies as many items as fit into the slice.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, SliceFull>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = NonZeroUsize::new(items.len().min(self.remaining())).ok_or(SliceFull)?;
+        let end = self.filled + amount.get();
+        self.slice[self.filled..end].copy_from_slice(&items[..amount.get()]);
+        self.filled = end;
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<(), SliceFull> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), SliceFull> {
+        Ok(())
+    }
+}
+
+impl<'a, T> SizedConsumer for SliceConsumer<'a, T> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+/// Consumes items by writing them into a slice of uninitialized memory, front to back.
+///
+/// Once the slice is full, `consume` drops the item and returns `Err(SliceFull)`. `flush` and
+/// `close` do not do anything. The written items are never dropped by the consumer, they belong to
+/// the caller once they are retrieved with [`UninitSliceConsumer::into_initialized`].
+#[derive(Debug)]
+pub struct UninitSliceConsumer<'a, T> {
+    slice: &'a mut [MaybeUninit<T>],
+    filled: usize,
+}
+
+impl<'a, T> UninitSliceConsumer<'a, T> {
+    pub fn new(slice: &'a mut [MaybeUninit<T>]) -> Self {
+        UninitSliceConsumer { slice, filled: 0 }
+    }
+
+    /// Returns how many items have been written.
+    pub fn filled(&self) -> usize {
+        self.filled
+    }
+
+    /// Returns how many more items can be written.
+    pub fn remaining(&self) -> usize {
+        self.slice.len() - self.filled
+    }
+
+    /// Returns the items that have been written.
+    pub fn initialized(&self) -> &[T] {
+        unsafe { &*(&self.slice[..self.filled] as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    /// Returns the items that have been written.
+    pub fn initialized_mut(&mut self) -> &mut [T] {
+        unsafe { &mut *(&mut self.slice[..self.filled] as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+
+    /// Returns the part of the slice that has been written to.
+    pub fn into_initialized(self) -> &'a mut [T] {
+        unsafe { &mut *(&mut self.slice[..self.filled] as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+
+    // Moves `amount` items, which must fit into the remaining space.
+    unsafe fn move_in(&mut self, items: *const T, amount: usize) {
+        let to = self.slice.as_mut_ptr().add(self.filled) as *mut T;
+        ptr::copy_nonoverlapping(items, to, amount);
+        self.filled += amount;
+    }
+}
+
+impl<'a, T> Consumer for UninitSliceConsumer<'a, T> {
+    type Item = T;
+    type Ex = ();
+    type In = SliceFull;
+
+    fn consume(&mut self, item: T) -> Result<(), SliceFull> {
+        let slot = self.slice.get_mut(self.filled).ok_or(SliceFull)?;
+        *slot = MaybeUninit::new(item);
+        self.filled += 1;
+        Ok(())
+    }
+
+    /// Copies as many items as fit into the slice.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, SliceFull>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = NonZeroUsize::new(items.len().min(self.remaining())).ok_or(SliceFull)?;
+        unsafe { self.move_in(items.as_ptr(), amount.get()) };
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<(), SliceFull> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), SliceFull> {
+        Ok(())
+    }
+}
+
+impl<'a, T> SizedConsumer for UninitSliceConsumer<'a, T> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+/// Moves as many items of the loaf as fit into the slice.
+impl<'a, T> ConsumerFromMany1 for UninitSliceConsumer<'a, T> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<T>,
+    ) -> Result<NonZeroUsize, SliceFull> {
+        let amount = cmp::min((*items).len(), self.remaining());
+        if amount == 0 {
+            drop(ptr::read(items as *const T));
+            return Err(SliceFull);
+        }
+
+        self.move_in(items as *const T, amount);
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+    use core::mem::ManuallyDrop;
+
+    use crate::testing::DropCounted;
+
+    // Moves the items of `items` into the consumer with `consume_from_many1`, then drops the items
+    // that have not been read. Returns what `consume_from_many1` returned.
+    fn move_from<'a, const M: usize>(
+        consumer: &mut UninitSliceConsumer<DropCounted<'a, usize>>,
+        items: [DropCounted<'a, usize>; M],
+    ) -> Result<NonZeroUsize, SliceFull> {
+        let mut items = ManuallyDrop::new(items);
+        let loaf = ptr::slice_from_raw_parts(items.as_ptr(), M - 1) as *const Loaf<_>;
+        let result = unsafe { consumer.consume_from_many1(loaf) };
+        let read = match &result {
+            Ok(amount) => amount.get(),
+            Err(_) => 1,
+        };
+        unsafe { ptr::drop_in_place(&mut items[read..]) };
+        result
+    }
+
+    #[test]
+    fn consume_until_full() {
+        let mut buf = [0; 2];
+        let mut consumer = SliceConsumer::new(&mut buf);
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.consume(2), Ok(()));
+        assert_eq!(consumer.remaining(), 0);
+        assert_eq!(consumer.consume(3), Err(SliceFull));
+        assert_eq!(consumer.filled(), 2);
+        assert_eq!(consumer.into_filled(), [1, 2]);
+    }
+
+    #[test]
+    fn consume_many_caps_at_remaining() {
+        let mut buf = [0; 3];
+        let mut consumer = SliceConsumer::new(&mut buf);
+        assert_eq!(consumer.consume_many(&[1]).map(NonZeroUsize::get), Ok(1));
+        assert_eq!(
+            consumer.consume_many(&[2, 3, 4]).map(NonZeroUsize::get),
+            Ok(2)
+        );
+        assert_eq!(consumer.consume_many(&[5]), Err(SliceFull));
+        assert_eq!(buf, [1, 2, 3]);
+    }
+
+    #[test]
+    fn overwritten_and_rejected_items_are_dropped() {
+        let drops = Cell::new(0);
+        let mut buf = [DropCounted::new(10, &drops), DropCounted::new(11, &drops)];
+        let mut consumer = SliceConsumer::new(&mut buf);
+
+        assert_eq!(consumer.consume(DropCounted::new(0, &drops)), Ok(()));
+        assert_eq!(drops.get(), 1);
+        assert_eq!(consumer.consume(DropCounted::new(1, &drops)), Ok(()));
+        assert_eq!(
+            consumer.consume(DropCounted::new(2, &drops)),
+            Err(SliceFull)
+        );
+        assert_eq!(drops.get(), 3);
+        assert!(consumer
+            .into_filled()
+            .iter()
+            .map(|item| item.value)
+            .eq(0..2));
+    }
+
+    #[test]
+    fn uninit_consume_from_many1_moves_what_fits() {
+        let drops = Cell::new(0);
+        let mut buf: [MaybeUninit<DropCounted<usize>>; 3] = [
+            MaybeUninit::uninit(),
+            MaybeUninit::uninit(),
+            MaybeUninit::uninit(),
+        ];
+        let mut consumer = UninitSliceConsumer::new(&mut buf);
+
+        let items = [0, 1].map(|i| DropCounted::new(i, &drops));
+        assert_eq!(
+            move_from(&mut consumer, items).map(NonZeroUsize::get),
+            Ok(2)
+        );
+        let items = [2, 3, 4].map(|i| DropCounted::new(i, &drops));
+        assert_eq!(
+            move_from(&mut consumer, items).map(NonZeroUsize::get),
+            Ok(1)
+        );
+        assert_eq!(drops.get(), 2);
+        assert_eq!(consumer.remaining(), 0);
+
+        let values = consumer.initialized().iter().map(|item| item.value);
+        assert!(values.eq(0..3));
+
+        let written = consumer.into_initialized();
+        unsafe { ptr::drop_in_place(written) };
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn uninit_full_drops_first_item_once() {
+        let drops = Cell::new(0);
+        let mut buf: [MaybeUninit<DropCounted<usize>>; 1] = [MaybeUninit::uninit()];
+        let mut consumer = UninitSliceConsumer::new(&mut buf);
+        assert_eq!(consumer.consume(DropCounted::new(0, &drops)), Ok(()));
+
+        let items = [1, 2, 3].map(|i| DropCounted::new(i, &drops));
+        assert_eq!(move_from(&mut consumer, items), Err(SliceFull));
+        assert_eq!(drops.get(), 3);
+
+        assert_eq!(
+            consumer.consume(DropCounted::new(4, &drops)),
+            Err(SliceFull)
+        );
+        assert_eq!(drops.get(), 4);
+
+        unsafe { ptr::drop_in_place(consumer.into_initialized()) };
+        assert_eq!(drops.get(), 5);
+    }
+}
@@ -0,0 +1,311 @@
+use core::convert::Infallible;
+
+use alloc::string::String;
+
+use crate::consumer::Consumer;
+use crate::util::{is_utf8_continuation, utf8_width};
+
+/// Consumes characters by appending them to a string. Never performs an internal state change.
+#[derive(Debug, Clone, Default)]
+pub struct StringConsumer {
+    string: String,
+}
+
+impl StringConsumer {
+    pub fn new() -> Self {
+        StringConsumer::default()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    pub fn into_string(self) -> String {
+        self.string
+    }
+}
+
+impl Consumer for StringConsumer {
+    type Item = char;
+    type Ex = ();
+    type In = Infallible;
+
+    fn consume(&mut self, item: char) -> Result<(), Infallible> {
+        self.string.push(item);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// The internal state change of a [`Utf8StringConsumer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Utf8StringError {
+    /// The bytes starting at the given offset are not valid UTF-8.
+    Invalid { offset: usize },
+    /// The consumer was closed in the middle of a `char` starting at the given offset.
+    Incomplete { offset: usize },
+}
+
+/// Consumes UTF-8 encoded bytes by decoding them and appending the result to a string.
+///
+/// Bytes of a `char` that is split across several calls to `consume` are held back until the
+/// `char` is complete. An invalid byte sequence results in `Err(Utf8StringError::Invalid)` and is
+/// discarded. If the sequence was only shown to be invalid by the byte following it, that byte is
+/// not consumed, so it can be passed to `consume` again to continue decoding with it. Closing the
+/// consumer in the middle of a `char` results in `Err(Utf8StringError::Incomplete)`. The consumer
+/// can keep being used after either internal state change.
+///
+/// A consumer created with [`lossy`](Utf8StringConsumer::lossy) appends
+/// `U+FFFD REPLACEMENT CHARACTER` instead of performing either internal state change, delimiting
+/// invalid sequences the same way as [`String::from_utf8_lossy`].
+#[derive(Debug, Clone)]
+pub struct Utf8StringConsumer {
+    string: String,
+    lossy: bool,
+    // The first `len` bytes of the `char` that is currently being decoded, which is `width` bytes
+    // long in total.
+    buf: [u8; 4],
+    len: usize,
+    width: usize,
+    offset: usize,
+}
+
+impl Utf8StringConsumer {
+    pub fn new() -> Self {
+        Utf8StringConsumer {
+            string: String::new(),
+            lossy: false,
+            buf: [0; 4],
+            len: 0,
+            width: 0,
+            offset: 0,
+        }
+    }
+
+    /// Creates a consumer that appends `U+FFFD REPLACEMENT CHARACTER` for invalid and incomplete
+    /// byte sequences rather than performing an internal state change.
+    pub fn lossy() -> Self {
+        Utf8StringConsumer {
+            lossy: true,
+            ..Utf8StringConsumer::new()
+        }
+    }
+
+    /// Returns the string decoded so far, without the bytes of an incomplete `char`.
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    /// Returns the bytes of the incomplete `char` that is currently being decoded.
+    pub fn pending(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the decoded string. Bytes of an incomplete `char` are lost.
+    pub fn into_string(self) -> String {
+        self.string
+    }
+
+    // Discards the bytes of the current `char`, which are not valid.
+    fn invalid(&mut self, err: Utf8StringError) -> Result<(), Utf8StringError> {
+        self.len = 0;
+        if self.lossy {
+            self.string.push(char::REPLACEMENT_CHARACTER);
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    fn start(&mut self, byte: u8) -> Result<(), Utf8StringError> {
+        let offset = self.offset;
+        self.offset += 1;
+        match utf8_width(byte) {
+            Some(1) => {
+                self.string.push(byte as char);
+                Ok(())
+            }
+            Some(width) => {
+                self.buf[0] = byte;
+                self.len = 1;
+                self.width = width;
+                Ok(())
+            }
+            None => self.invalid(Utf8StringError::Invalid { offset }),
+        }
+    }
+}
+
+impl Default for Utf8StringConsumer {
+    fn default() -> Self {
+        Utf8StringConsumer::new()
+    }
+}
+
+impl Consumer for Utf8StringConsumer {
+    type Item = u8;
+    type Ex = ();
+    type In = Utf8StringError;
+
+    fn consume(&mut self, item: u8) -> Result<(), Utf8StringError> {
+        if self.len == 0 {
+            return self.start(item);
+        }
+
+        if !is_utf8_continuation(&self.buf[..self.len], item) {
+            // The byte is not part of the invalid sequence, decoding resumes with it.
+            let offset = self.offset - self.len;
+            self.invalid(Utf8StringError::Invalid { offset })?;
+            return self.start(item);
+        }
+
+        self.buf[self.len] = item;
+        self.len += 1;
+        self.offset += 1;
+        if self.len == self.width {
+            // The bytes have been validated above.
+            let s = core::str::from_utf8(&self.buf[..self.width]).unwrap_or("\u{FFFD}");
+            self.string.push_str(s);
+            self.len = 0;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Utf8StringError> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Utf8StringError> {
+        if self.len == 0 {
+            return Ok(());
+        }
+
+        let offset = self.offset - self.len;
+        self.invalid(Utf8StringError::Incomplete { offset })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    // Feeds the bytes one at a time to a strict consumer, resuming after every internal state
+    // change and recording the errors.
+    fn decode_strict(bytes: &[u8]) -> (String, Vec<Utf8StringError>) {
+        let mut consumer = Utf8StringConsumer::new();
+        let mut errors = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let offset = consumer.offset;
+            match consumer.consume(bytes[i]) {
+                Ok(()) => i += 1,
+                Err(e) => {
+                    errors.push(e);
+                    // The offending byte is consumed only if it was invalid on its own.
+                    if consumer.offset > offset {
+                        i += 1;
+                    }
+                }
+            }
+        }
+        if let Err(e) = consumer.close(()) {
+            errors.push(e);
+        }
+        (consumer.into_string(), errors)
+    }
+
+    fn decode_lossy(bytes: &[u8]) -> String {
+        let mut consumer = Utf8StringConsumer::lossy();
+        for byte in bytes {
+            consumer.consume(*byte).unwrap();
+        }
+        consumer.close(()).unwrap();
+        consumer.into_string()
+    }
+
+    #[test]
+    fn split_multi_byte_chars() {
+        let text = "aé€😀";
+        let mut consumer = Utf8StringConsumer::new();
+        let bytes = text.as_bytes();
+
+        consumer.consume(bytes[0]).unwrap();
+        consumer.consume(bytes[1]).unwrap();
+        assert_eq!(consumer.as_str(), "a");
+        assert_eq!(consumer.pending(), &bytes[1..2]);
+        for byte in &bytes[2..] {
+            consumer.consume(*byte).unwrap();
+        }
+        assert_eq!(consumer.pending(), &[]);
+        consumer.close(()).unwrap();
+        assert_eq!(consumer.into_string(), text);
+    }
+
+    #[test]
+    fn strict_keeps_byte_following_invalid_sequence() {
+        let mut consumer = Utf8StringConsumer::new();
+        consumer.consume(0xE0).unwrap();
+        assert_eq!(
+            consumer.consume(b'A'),
+            Err(Utf8StringError::Invalid { offset: 0 })
+        );
+        consumer.consume(b'A').unwrap();
+        assert_eq!(consumer.into_string(), "A");
+
+        assert_eq!(
+            decode_strict(&[0xE0, b'A', 0xFF, 0xC3, 0xA9]),
+            (
+                String::from("Aé"),
+                alloc::vec![
+                    Utf8StringError::Invalid { offset: 0 },
+                    Utf8StringError::Invalid { offset: 2 },
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn incomplete_at_close() {
+        let mut consumer = Utf8StringConsumer::new();
+        for byte in [b'a', 0xF0, 0x9F] {
+            consumer.consume(byte).unwrap();
+        }
+        assert_eq!(
+            consumer.close(()),
+            Err(Utf8StringError::Incomplete { offset: 1 })
+        );
+        assert_eq!(consumer.as_str(), "a");
+
+        assert_eq!(decode_lossy(&[b'a', 0xF0, 0x9F]), "a\u{FFFD}");
+    }
+
+    #[test]
+    fn strict_and_lossy_agree_with_from_utf8_lossy() {
+        let inputs: [&[u8]; 6] = [
+            b"plain",
+            &[0xE0, b'A'],
+            &[0xF0, 0x9F, 0x98, b'x', 0x80],
+            &[0xED, 0xA0, 0x80, b'z'],
+            &[0xC3, 0xC3, 0xA9],
+            &[0xE2, 0x82],
+        ];
+        for input in inputs {
+            let expected = String::from_utf8_lossy(input);
+            assert_eq!(decode_lossy(input), expected);
+
+            let (strict, errors) = decode_strict(input);
+            let valid: String = expected.chars().filter(|c| *c != '\u{FFFD}').collect();
+            assert_eq!(strict, valid);
+            assert_eq!(errors.len(), expected.matches('\u{FFFD}').count());
+        }
+    }
+}
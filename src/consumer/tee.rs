@@ -0,0 +1,93 @@
+use crate::consumer::Consumer;
+use crate::Either;
+
+/// Consumes every item with two consumers, the first one receiving a clone.
+///
+/// If the first consumer performs an internal state change, the second one is not called. If the
+/// first consumer succeeds but the second one performs an internal state change, the item has
+/// still been consumed by the first one.
+#[derive(Debug, Clone)]
+pub struct TeeConsumer<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> TeeConsumer<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        TeeConsumer { a, b }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B> Consumer for TeeConsumer<A, B>
+where
+    A: Consumer,
+    A::Item: Clone,
+    B: Consumer<Item = A::Item>,
+{
+    type Item = A::Item;
+    type Ex = (A::Ex, B::Ex);
+    type In = Either<A::In, B::In>;
+
+    fn consume(&mut self, item: A::Item) -> Result<(), Self::In> {
+        self.a.consume(item.clone()).map_err(Either::Left)?;
+        self.b.consume(item).map_err(Either::Right)
+    }
+
+    /// Flushes both consumers, even if flushing the first one fails.
+    fn flush(&mut self) -> Result<(), Self::In> {
+        let a = self.a.flush().map_err(Either::Left);
+        let b = self.b.flush().map_err(Either::Right);
+        a.and(b)
+    }
+
+    /// Closes both consumers, even if closing the first one fails.
+    fn close(&mut self, (reason_a, reason_b): Self::Ex) -> Result<(), Self::In> {
+        let a = self.a.close(reason_a).map_err(Either::Left);
+        let b = self.b.close(reason_b).map_err(Either::Right);
+        a.and(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ConsumerCall, ConsumerResponse, ScriptedConsumer};
+
+    #[test]
+    fn flush_reaches_second_when_first_fails() {
+        let mut consumer = TeeConsumer::new(
+            ScriptedConsumer::<u8, &str, ()>::new([ConsumerResponse::StateChange("a")]),
+            ScriptedConsumer::<u8, &str, ()>::new([ConsumerResponse::Accept]),
+        );
+        assert_eq!(consumer.flush(), Err(Either::Left("a")));
+
+        let (a, b) = consumer.into_inner();
+        assert_eq!(a.log(), [ConsumerCall::Flush]);
+        assert_eq!(b.log(), [ConsumerCall::Flush]);
+    }
+
+    #[test]
+    fn consume_skips_second_when_first_fails() {
+        let mut consumer = TeeConsumer::new(
+            ScriptedConsumer::<u8, &str, ()>::new([
+                ConsumerResponse::Accept,
+                ConsumerResponse::StateChange("a"),
+            ]),
+            ScriptedConsumer::<u8, &str, ()>::new([]),
+        );
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.consume(2), Err(Either::Left("a")));
+
+        let (a, b) = consumer.into_inner();
+        assert_eq!(
+            a.log(),
+            [ConsumerCall::Consume(1), ConsumerCall::Consume(2)]
+        );
+        assert_eq!(b.log(), [ConsumerCall::Consume(1)]);
+    }
+}
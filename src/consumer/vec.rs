@@ -0,0 +1,203 @@
+use core::cmp;
+use core::convert::Infallible;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use alloc::vec::Vec;
+use loaf::Loaf;
+
+use crate::consumer::{Consumer, ConsumerFromMany1, LimitReached, SizedConsumer};
+
+/// Consumes items by pushing them onto the vector. Closing shrinks the vector's capacity to fit
+/// its length.
+impl<T> Consumer for Vec<T> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn consume(&mut self, item: T) -> Result<(), Infallible> {
+        self.push(item);
+        Ok(())
+    }
+
+    /// Always consumes all items.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, Infallible>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        self.extend_from_slice(items);
+        Ok(unsafe { NonZeroUsize::new_unchecked(items.len()) })
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), Infallible> {
+        self.shrink_to_fit();
+        Ok(())
+    }
+}
+
+/// Accepts any number of items.
+impl<T> SizedConsumer for Vec<T> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Always consumes all items of the loaf.
+impl<T> ConsumerFromMany1 for Vec<T> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<T>,
+    ) -> Result<NonZeroUsize, Infallible> {
+        let amount = (*items).len();
+        self.reserve(amount);
+        ptr::copy_nonoverlapping(items as *const T, self.as_mut_ptr().add(self.len()), amount);
+        self.set_len(self.len() + amount);
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+/// Consumes items by pushing them onto a vector that may hold at most a fixed number of items.
+///
+/// Once the limit has been reached, `consume` drops the item and returns `Err(LimitReached)`.
+/// Without a limit this behaves like the [`Consumer`] implementation of `Vec` itself. `flush` does
+/// not do anything, `close` shrinks the vector's capacity to fit its length.
+#[derive(Debug, Clone)]
+pub struct VecConsumer<T> {
+    vec: Vec<T>,
+    limit: usize,
+}
+
+impl<T> VecConsumer<T> {
+    /// Creates a consumer without a limit.
+    pub fn new() -> Self {
+        VecConsumer::with_limit(usize::MAX)
+    }
+
+    /// Creates a consumer that accepts at most `limit` items.
+    pub fn with_limit(limit: usize) -> Self {
+        VecConsumer {
+            vec: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Returns how many more items can be consumed.
+    pub fn remaining(&self) -> usize {
+        self.limit - self.vec.len()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.vec
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.vec
+    }
+}
+
+impl<T> Default for VecConsumer<T> {
+    fn default() -> Self {
+        VecConsumer::new()
+    }
+}
+
+impl<T> Consumer for VecConsumer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = LimitReached;
+
+    fn consume(&mut self, item: T) -> Result<(), LimitReached> {
+        if self.remaining() == 0 {
+            return Err(LimitReached);
+        }
+        self.vec.push(item);
+        Ok(())
+    }
+
+    /// Copies as many items as the limit allows.
+    fn consume_many(&mut self, items: &[T]) -> Result<NonZeroUsize, LimitReached>
+    where
+        T: Copy,
+    {
+        assert!(!items.is_empty(), "called consume_many with no items");
+        let amount = cmp::min(items.len(), self.remaining());
+        let amount = NonZeroUsize::new(amount).ok_or(LimitReached)?;
+        self.vec.extend_from_slice(&items[..amount.get()]);
+        Ok(amount)
+    }
+
+    fn flush(&mut self) -> Result<(), LimitReached> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), LimitReached> {
+        self.vec.shrink_to_fit();
+        Ok(())
+    }
+}
+
+impl<T> SizedConsumer for VecConsumer<T> {
+    fn capacity_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+/// Consumes as many items of the loaf as the limit allows.
+impl<T> ConsumerFromMany1 for VecConsumer<T> {
+    unsafe fn consume_from_many1(
+        &mut self,
+        items: *const Loaf<T>,
+    ) -> Result<NonZeroUsize, LimitReached> {
+        let amount = cmp::min((*items).len(), self.remaining());
+        if amount == 0 {
+            drop(ptr::read(items as *const T));
+            return Err(LimitReached);
+        }
+
+        self.vec.reserve(amount);
+        let end = self.vec.as_mut_ptr().add(self.vec.len());
+        ptr::copy_nonoverlapping(items as *const T, end, amount);
+        self.vec.set_len(self.vec.len() + amount);
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::pipe;
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::PipeError;
+
+    #[test]
+    fn slice_round_trip() {
+        let input = [1, 2, 3, 4, 5];
+        let mut producer = SliceProducer::new(&input);
+        let mut consumer = VecConsumer::new();
+
+        assert_eq!(
+            pipe(&mut producer, &mut consumer),
+            Err(PipeError::Producer(SliceEnd))
+        );
+        assert_eq!(consumer.into_vec(), input);
+    }
+
+    #[test]
+    fn slice_round_trip_with_limit() {
+        let input = [1, 2, 3, 4, 5];
+        let mut producer = SliceProducer::new(&input);
+        let mut consumer = VecConsumer::with_limit(3);
+
+        assert_eq!(
+            pipe(&mut producer, &mut consumer),
+            Err(PipeError::Consumer(LimitReached))
+        );
+        assert_eq!(consumer.into_vec(), [1, 2, 3]);
+    }
+}
@@ -1,5 +1,38 @@
 #![no_std]
 
+#[cfg(any(feature = "alloc", test))]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "async")]
+mod async_consumer;
+#[cfg(feature = "async")]
+mod async_producer;
+mod collect;
+pub mod consumer;
+mod pipe;
+pub mod producer;
+mod spsc;
+#[cfg(any(feature = "testing", test))]
+/// Scripted producers and consumers for testing code that is generic over them.
+pub mod testing;
+mod util;
+/// An experimental version of the producer API that reports the end of a sequence separately from
+/// errors.
+pub mod v2;
+
+#[cfg(feature = "async")]
+pub use async_consumer::{AsyncConsumer, SyncAsConsumer};
+#[cfg(feature = "async")]
+pub use async_producer::{AsyncProducer, SyncAsProducer};
+pub use collect::{collect, FromProducer};
+pub use consumer::ConsumerExt;
+pub use pipe::{pipe, pipe_flushed, pipe_n, PipeError};
+pub use producer::ProducerExt;
+pub use spsc::{SpscConsumer, SpscEmpty, SpscFull, SpscProducer, SpscQueue};
+pub use util::Either;
+
 use core::num::NonZeroUsize;
 
 use loaf::Loaf;
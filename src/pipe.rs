@@ -0,0 +1,145 @@
+use crate::consumer::Consumer;
+use crate::producer::Producer;
+
+/// Which side of a pipe performed an internal state change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PipeError<P, C> {
+    Producer(P),
+    Consumer(C),
+}
+
+/// Moves items from the producer into the consumer until either of them performs an internal
+/// state change.
+///
+/// Since producers signal the end of their sequence through an internal state change, this only
+/// ever returns an `Err`. If the consumer performs the internal state change, the item it was
+/// given is lost.
+pub fn pipe<P, C>(producer: &mut P, consumer: &mut C) -> Result<(), PipeError<P::In, C::In>>
+where
+    P: Producer,
+    C: Consumer<Item = P::Item>,
+{
+    loop {
+        transfer(producer, consumer)?;
+    }
+}
+
+/// Moves at most `n` items from the producer into the consumer, returning `Ok(())` if exactly
+/// `n` items have been moved.
+pub fn pipe_n<P, C>(
+    producer: &mut P,
+    consumer: &mut C,
+    n: usize,
+) -> Result<(), PipeError<P::In, C::In>>
+where
+    P: Producer,
+    C: Consumer<Item = P::Item>,
+{
+    for _ in 0..n {
+        transfer(producer, consumer)?;
+    }
+    Ok(())
+}
+
+/// Like [`pipe`], but flushes the consumer after every item.
+pub fn pipe_flushed<P, C>(producer: &mut P, consumer: &mut C) -> Result<(), PipeError<P::In, C::In>>
+where
+    P: Producer,
+    C: Consumer<Item = P::Item>,
+{
+    loop {
+        transfer(producer, consumer)?;
+        consumer.flush().map_err(PipeError::Consumer)?;
+    }
+}
+
+fn transfer<P, C>(producer: &mut P, consumer: &mut C) -> Result<(), PipeError<P::In, C::In>>
+where
+    P: Producer,
+    C: Consumer<Item = P::Item>,
+{
+    let item = producer.produce().map_err(PipeError::Producer)?;
+    consumer.consume(item).map_err(PipeError::Consumer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::consumer::{CountingConsumer, SliceConsumer, SliceFull};
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ConsumerCall, ConsumerResponse, ScriptedConsumer};
+
+    #[test]
+    fn producer_end() {
+        let mut producer = SliceProducer::new(&[1, 2, 3]);
+        let mut buf = [0; 4];
+        let mut consumer = SliceConsumer::new(&mut buf);
+
+        assert_eq!(
+            pipe(&mut producer, &mut consumer),
+            Err(PipeError::Producer(SliceEnd))
+        );
+        assert_eq!(buf, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn consumer_full() {
+        let mut producer = SliceProducer::new(&[1, 2, 3]);
+        let mut buf = [0; 2];
+        let mut consumer = SliceConsumer::new(&mut buf);
+
+        assert_eq!(
+            pipe(&mut producer, &mut consumer),
+            Err(PipeError::Consumer(SliceFull))
+        );
+        // The third item was produced and lost.
+        assert_eq!(producer.produce(), Err(SliceEnd));
+        assert_eq!(buf, [1, 2]);
+    }
+
+    #[test]
+    fn pipe_n_moves_exactly_n_items() {
+        let mut producer = SliceProducer::new(&[1, 2, 3, 4]);
+        let mut consumer = ScriptedConsumer::<u8, (), ()>::new([
+            ConsumerResponse::Accept,
+            ConsumerResponse::Accept,
+        ]);
+
+        assert_eq!(pipe_n(&mut producer, &mut consumer, 2), Ok(()));
+        assert_eq!(
+            consumer.log(),
+            &[ConsumerCall::Consume(1), ConsumerCall::Consume(2)]
+        );
+        assert_eq!(producer.produce(), Ok(3));
+
+        assert_eq!(pipe_n(&mut producer, &mut consumer, 0), Ok(()));
+        assert_eq!(consumer.log().len(), 2);
+    }
+
+    #[test]
+    fn pipe_n_stops_early() {
+        let mut producer = SliceProducer::new(&[1]);
+        let mut buf = [0; 4];
+        let mut consumer = SliceConsumer::new(&mut buf);
+
+        assert_eq!(
+            pipe_n(&mut producer, &mut consumer, 3),
+            Err(PipeError::Producer(SliceEnd))
+        );
+    }
+
+    #[test]
+    fn pipe_flushed_flushes_after_every_item() {
+        let mut producer = SliceProducer::new(&[1, 2, 3]);
+        let mut buf = [0; 4];
+        let mut consumer = CountingConsumer::new(SliceConsumer::new(&mut buf));
+
+        assert_eq!(
+            pipe_flushed(&mut producer, &mut consumer),
+            Err(PipeError::Producer(SliceEnd))
+        );
+        assert_eq!(consumer.items(), 3);
+        assert_eq!(consumer.flushes(), 3);
+    }
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+
+use crate::producer::Producer;
+
+/// Produces the items of an array by value.
+///
+/// After producing the last item or after `stop` (which drops all items that have not been
+/// produced), `produce` keeps returning `Err(())`.
+pub struct ArrayProducer<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    // The items in `start..end` are initialized, all others have been moved out or dropped.
+    start: usize,
+    end: usize,
+}
+
+impl<T, const N: usize> ArrayProducer<T, N> {
+    pub fn new(items: [T; N]) -> Self {
+        let items = ManuallyDrop::new(items);
+        ArrayProducer {
+            items: unsafe { ptr::read(&*items as *const [T; N] as *const [MaybeUninit<T>; N]) },
+            start: 0,
+            end: N,
+        }
+    }
+
+    /// Returns the items that have not been produced yet.
+    pub fn as_slice(&self) -> &[T] {
+        let remaining = &self.items[self.start..self.end];
+        unsafe { &*(remaining as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    fn drop_remaining(&mut self) {
+        let remaining = &mut self.items[self.start..self.end];
+        self.start = self.end;
+        unsafe { ptr::drop_in_place(remaining as *mut [MaybeUninit<T>] as *mut [T]) };
+    }
+}
+
+impl<T, const N: usize> Producer for ArrayProducer<T, N> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        if self.start == self.end {
+            return Err(());
+        }
+
+        let item = unsafe { self.items[self.start].assume_init_read() };
+        self.start += 1;
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.drop_remaining();
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayProducer<T, N> {
+    fn drop(&mut self) {
+        self.drop_remaining();
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayProducer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayProducer")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::testing::DropCounted;
+
+    fn counted<const N: usize>(drops: &Cell<usize>) -> ArrayProducer<DropCounted<'_, usize>, N> {
+        ArrayProducer::new(core::array::from_fn(|i| DropCounted::new(i, drops)))
+    }
+
+    #[test]
+    fn full_consumption() {
+        let drops = Cell::new(0);
+        let mut producer = counted::<3>(&drops);
+        for i in 0..3 {
+            assert_eq!(producer.produce().map(|item| item.value), Ok(i));
+        }
+        assert_eq!(drops.get(), 3);
+        assert!(producer.produce().is_err());
+        assert!(producer.produce().is_err());
+        drop(producer);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn partial_consumption_then_drop() {
+        let drops = Cell::new(0);
+        let mut producer = counted::<4>(&drops);
+        let first = producer.produce().unwrap();
+        assert_eq!(producer.as_slice().len(), 3);
+        drop(producer);
+        assert_eq!(drops.get(), 3);
+        assert_eq!(first.value, 0);
+        drop(first);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn stop_then_drop() {
+        let drops = Cell::new(0);
+        let mut producer = counted::<4>(&drops);
+        let first = producer.produce().unwrap();
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(drops.get(), 3);
+        assert!(producer.as_slice().is_empty());
+        assert!(producer.produce().is_err());
+        drop(producer);
+        assert_eq!(drops.get(), 3);
+        drop(first);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn state_change_after_last_item() {
+        let mut producer = ArrayProducer::new([1, 2]);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.produce(), Err(()));
+
+        let mut producer = ArrayProducer::<u8, 0>::new([]);
+        assert_eq!(producer.produce(), Err(()));
+    }
+}
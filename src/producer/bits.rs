@@ -0,0 +1,158 @@
+use crate::producer::Producer;
+
+/// Produces the bits of the bytes of the wrapped producer, most significant bit first unless
+/// created with [`lsb_first`](BitsProducer::lsb_first).
+///
+/// A new byte is only produced from the wrapped producer once all bits of the current byte have
+/// been produced, its internal state changes are passed on unchanged. `stop` discards the
+/// remaining bits of the current byte.
+#[derive(Debug, Clone)]
+pub struct BitsProducer<P> {
+    producer: P,
+    byte: u8,
+    // How many bits of `byte` have not been produced yet.
+    remaining: u8,
+    lsb_first: bool,
+}
+
+impl<P> BitsProducer<P> {
+    pub fn new(producer: P) -> Self {
+        BitsProducer {
+            producer,
+            byte: 0,
+            remaining: 0,
+            lsb_first: false,
+        }
+    }
+
+    /// Creates a producer that produces the least significant bit of each byte first.
+    pub fn lsb_first(producer: P) -> Self {
+        BitsProducer {
+            lsb_first: true,
+            ..BitsProducer::new(producer)
+        }
+    }
+
+    /// Returns how many bits of the current byte have been produced, between 0 (at a byte
+    /// boundary) and 7.
+    pub fn bit_offset(&self) -> u8 {
+        (8 - self.remaining) % 8
+    }
+
+    /// Discards the remaining bits of the current byte, so that the next bit is the first bit of
+    /// the next byte. Does nothing at a byte boundary.
+    pub fn align(&mut self) {
+        self.remaining = 0;
+    }
+
+    /// Returns the wrapped producer. The remaining bits of the current byte are lost.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer<Item = u8>> Producer for BitsProducer<P> {
+    type Item = bool;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<bool, P::In> {
+        if self.remaining == 0 {
+            self.byte = self.producer.produce()?;
+            self.remaining = 8;
+        }
+
+        self.remaining -= 1;
+        let shift = if self.lsb_first {
+            7 - self.remaining
+        } else {
+            self.remaining
+        };
+        Ok((self.byte >> shift) & 1 == 1)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.remaining = 0;
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    fn next_bits<P: Producer<Item = bool>>(producer: &mut P, n: usize) -> [bool; 16] {
+        let mut bits = [false; 16];
+        for bit in bits.iter_mut().take(n) {
+            *bit = producer.produce().ok().unwrap();
+        }
+        bits
+    }
+
+    fn expected(bits: &[u8]) -> [bool; 16] {
+        let mut out = [false; 16];
+        for (o, b) in out.iter_mut().zip(bits) {
+            *o = *b == 1;
+        }
+        out
+    }
+
+    #[test]
+    fn msb_first() {
+        let mut producer = BitsProducer::new(SliceProducer::new(&[0b1000_0110, 0b0000_0001]));
+        assert_eq!(
+            next_bits(&mut producer, 16),
+            expected(&[1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1])
+        );
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    #[test]
+    fn lsb_first() {
+        let mut producer = BitsProducer::lsb_first(SliceProducer::new(&[0b1000_0110, 0b0000_0001]));
+        assert_eq!(
+            next_bits(&mut producer, 16),
+            expected(&[0, 1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    #[test]
+    fn bit_offset_and_align() {
+        let mut producer = BitsProducer::new(SliceProducer::new(&[0b1010_0000, 0b0100_0000]));
+        assert_eq!(producer.bit_offset(), 0);
+        assert_eq!(producer.produce(), Ok(true));
+        assert_eq!(producer.produce(), Ok(false));
+        assert_eq!(producer.bit_offset(), 2);
+
+        producer.align();
+        assert_eq!(producer.bit_offset(), 0);
+        assert_eq!(producer.produce(), Ok(false));
+        assert_eq!(producer.produce(), Ok(true));
+
+        // Aligning at a byte boundary does nothing.
+        let mut producer = BitsProducer::new(SliceProducer::new(&[0xFF, 0x00]));
+        next_bits(&mut producer, 8);
+        producer.align();
+        assert_eq!(producer.produce(), Ok(false));
+    }
+
+    #[test]
+    fn state_change_only_between_bytes() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(0xFF),
+            ProduceOutcome::StateChange("in"),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let mut producer = BitsProducer::new(inner);
+        assert_eq!(next_bits(&mut producer, 8), expected(&[1; 8]));
+        assert_eq!(producer.produce(), Err("in"));
+    }
+}
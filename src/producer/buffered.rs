@@ -0,0 +1,282 @@
+use core::fmt;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+
+use crate::producer::Producer;
+
+/// Produces the items of the wrapped producer, taking them from it up to `N` at a time and
+/// buffering them in an inline array.
+///
+/// `slurp` slurps the wrapped producer and then fills the free space of the buffer. An internal
+/// state change of the wrapped producer while filling the buffer is passed on once all items that
+/// were buffered before it have been produced. `stop` drops all buffered items and stops the
+/// wrapped producer.
+pub struct BufferedProducer<P: Producer, const N: usize> {
+    producer: P,
+    // The items in `start..end` are initialized, all others have been moved out or dropped.
+    buf: [MaybeUninit<P::Item>; N],
+    start: usize,
+    end: usize,
+    pending: Option<P::In>,
+}
+
+impl<P: Producer, const N: usize> BufferedProducer<P, N> {
+    /// Panics if `N` is zero.
+    pub fn new(producer: P) -> Self {
+        assert!(N > 0, "BufferedProducer requires a nonzero capacity");
+        BufferedProducer {
+            producer,
+            buf: [const { MaybeUninit::uninit() }; N],
+            start: 0,
+            end: 0,
+            pending: None,
+        }
+    }
+
+    /// Returns the items that have been buffered but not produced yet.
+    pub fn buffer(&self) -> &[P::Item] {
+        let buffered = &self.buf[self.start..self.end];
+        unsafe { &*(buffered as *const [MaybeUninit<P::Item>] as *const [P::Item]) }
+    }
+
+    /// Returns the wrapped producer. Buffered items and a pending internal state change are
+    /// dropped.
+    pub fn into_inner(self) -> P {
+        let mut this = ManuallyDrop::new(self);
+        this.drop_buffered();
+        this.pending = None;
+        unsafe { ptr::read(&this.producer) }
+    }
+
+    // Moves the buffered items to the front, then produces items until the buffer is full or the
+    // wrapped producer performs an internal state change.
+    fn fill(&mut self) {
+        let buffered = self.end - self.start;
+        if self.start > 0 {
+            unsafe {
+                let base = self.buf.as_mut_ptr();
+                ptr::copy(base.add(self.start), base, buffered);
+            }
+            self.start = 0;
+            self.end = buffered;
+        }
+
+        while self.pending.is_none() && self.end < N {
+            match self.producer.produce() {
+                Ok(item) => {
+                    self.buf[self.end] = MaybeUninit::new(item);
+                    self.end += 1;
+                }
+                Err(e) => self.pending = Some(e),
+            }
+        }
+    }
+
+    fn drop_buffered(&mut self) {
+        let buffered = &mut self.buf[self.start..self.end];
+        self.start = 0;
+        self.end = 0;
+        unsafe { ptr::drop_in_place(buffered as *mut [MaybeUninit<P::Item>] as *mut [P::Item]) };
+    }
+}
+
+impl<P: Producer, const N: usize> Producer for BufferedProducer<P, N> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        if self.start == self.end {
+            if let Some(e) = self.pending.take() {
+                return Err(e);
+            }
+
+            self.fill();
+            if self.start == self.end {
+                return Err(self.pending.take().unwrap());
+            }
+        }
+
+        let item = unsafe { self.buf[self.start].assume_init_read() };
+        self.start += 1;
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        if self.pending.is_none() {
+            self.producer.slurp()?;
+            self.fill();
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.drop_buffered();
+        self.pending = None;
+        self.producer.stop(reason)
+    }
+}
+
+impl<P: Producer, const N: usize> Drop for BufferedProducer<P, N> {
+    fn drop(&mut self) {
+        self.drop_buffered();
+    }
+}
+
+impl<P, const N: usize> fmt::Debug for BufferedProducer<P, N>
+where
+    P: Producer + fmt::Debug,
+    P::Item: fmt::Debug,
+    P::In: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedProducer")
+            .field("producer", &self.producer)
+            .field("buffer", &self.buffer())
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::producer::ArrayProducer;
+    use crate::testing::{DropCounted, ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    fn counted<'a, const N: usize>(drops: &'a Cell<usize>) -> [DropCounted<'a, usize>; N] {
+        core::array::from_fn(|i| DropCounted::new(i, drops))
+    }
+
+    #[test]
+    fn refills_when_empty() {
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::StateChange(()),
+        ]);
+        let mut producer = BufferedProducer::<_, 2>::new(inner);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.buffer(), [2]);
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.buffer(), []);
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err(()));
+
+        let inner = producer.into_inner();
+        assert_eq!(
+            inner.log(),
+            [
+                ProducerCall::Produce,
+                ProducerCall::Produce,
+                ProducerCall::Produce,
+                ProducerCall::Produce,
+            ]
+        );
+    }
+
+    #[test]
+    fn slurp_fills_free_space() {
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::Yield(4),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let mut producer = BufferedProducer::<_, 3>::new(inner);
+
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.buffer(), [1, 2, 3]);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.buffer(), [2, 3, 4]);
+
+        let inner = producer.into_inner();
+        assert_eq!(
+            inner.log(),
+            [
+                ProducerCall::Slurp,
+                ProducerCall::Produce,
+                ProducerCall::Produce,
+                ProducerCall::Produce,
+                ProducerCall::Slurp,
+                ProducerCall::Produce,
+            ]
+        );
+    }
+
+    #[test]
+    fn state_change_while_filling_comes_after_buffered_items() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::StateChange("end"),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::StateChange("end again"),
+        ]);
+        let mut producer = BufferedProducer::<_, 4>::new(inner);
+
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.buffer(), [1, 2]);
+        // With a pending internal state change, slurp does not touch the wrapped producer.
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err("end"));
+        // The wrapped producer is used again afterwards.
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err("end again"));
+    }
+
+    #[test]
+    fn stop_drops_buffered_items() {
+        let drops = Cell::new(0);
+        let mut producer = BufferedProducer::<_, 4>::new(ArrayProducer::new(counted::<6>(&drops)));
+
+        let first = producer.produce().unwrap();
+        assert_eq!(first.value, 0);
+        assert_eq!(drops.get(), 0);
+
+        // Three buffered items, plus the two items still in the wrapped producer.
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(drops.get(), 5);
+        assert!(producer.produce().is_err());
+
+        drop(producer);
+        assert_eq!(drops.get(), 5);
+        drop(first);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn drop_drops_buffered_items() {
+        let drops = Cell::new(0);
+        let mut producer = BufferedProducer::<_, 4>::new(ArrayProducer::new(counted::<4>(&drops)));
+
+        let first = producer.produce().unwrap();
+        assert_eq!(producer.buffer().len(), 3);
+        drop(producer);
+        assert_eq!(drops.get(), 3);
+        drop(first);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn into_inner_drops_buffered_items() {
+        let drops = Cell::new(0);
+        let mut producer = BufferedProducer::<_, 2>::new(ArrayProducer::new(counted::<5>(&drops)));
+
+        assert_eq!(producer.slurp(), Ok(()));
+        let inner = producer.into_inner();
+        assert_eq!(drops.get(), 2);
+        assert_eq!(inner.as_slice().len(), 3);
+        drop(inner);
+        assert_eq!(drops.get(), 5);
+    }
+}
@@ -0,0 +1,147 @@
+use core::cmp;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+use crate::producer::{Producer, ProducerTo};
+
+/// Produces the bytes of a byte slice.
+///
+/// After producing the last byte, `produce` keeps returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct ByteProducer<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> ByteProducer<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        ByteProducer { remaining: bytes }
+    }
+
+    /// Returns the bytes that have not been produced yet.
+    pub fn remaining_slice(&self) -> &'a [u8] {
+        self.remaining
+    }
+
+    // Copies as many bytes as fit into the `capacity` bytes starting at `to`.
+    unsafe fn copy_to(&mut self, to: *mut u8, capacity: usize) -> Result<NonZeroUsize, ()> {
+        let amount = cmp::min(self.remaining.len(), capacity);
+        if amount == 0 {
+            return Err(());
+        }
+
+        ptr::copy_nonoverlapping(self.remaining.as_ptr(), to, amount);
+        self.remaining = &self.remaining[amount..];
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+impl<'a> Producer for ByteProducer<'a> {
+    type Item = u8;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<u8, ()> {
+        let (first, rest) = self.remaining.split_first().ok_or(())?;
+        self.remaining = rest;
+        Ok(*first)
+    }
+
+    /// Copies as many bytes as fit into `buf`.
+    fn produce_many(&mut self, buf: &mut [MaybeUninit<u8>]) -> Result<NonZeroUsize, ()> {
+        assert!(!buf.is_empty(), "called produce_many with an empty buffer");
+        unsafe { self.copy_to(buf.as_mut_ptr() as *mut u8, buf.len()) }
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Copies as many bytes as fit into the loaf.
+impl<'a> ProducerTo for ByteProducer<'a> {
+    unsafe fn produce_to(&mut self, to: *mut Loaf<MaybeUninit<u8>>) -> Result<NonZeroUsize, ()> {
+        self.copy_to(to as *mut u8, (*to).len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    const BYTES: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+    fn drain_single(mut producer: ByteProducer) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while let Ok(byte) = producer.produce() {
+            bytes.push(byte);
+        }
+        bytes
+    }
+
+    fn drain_many(mut producer: ByteProducer, chunk: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut buf = [MaybeUninit::uninit(); 8];
+        while let Ok(amount) = producer.produce_many(&mut buf[..chunk]) {
+            for slot in &buf[..amount.get()] {
+                bytes.push(unsafe { slot.assume_init() });
+            }
+        }
+        bytes
+    }
+
+    fn drain_to(mut producer: ByteProducer, chunk: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut buf = [MaybeUninit::uninit(); 8];
+        loop {
+            // A loaf of `chunk` slots is a slice of its `chunk - 1` trailing slots.
+            let to = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr(), chunk - 1)
+                as *mut Loaf<MaybeUninit<u8>>;
+            match unsafe { producer.produce_to(to) } {
+                Ok(amount) => {
+                    for slot in &buf[..amount.get()] {
+                        bytes.push(unsafe { slot.assume_init() });
+                    }
+                }
+                Err(()) => return bytes,
+            }
+        }
+    }
+
+    #[test]
+    fn bulk_and_single_paths_agree() {
+        for len in 0..=BYTES.len() {
+            let bytes = &BYTES[..len];
+            let single = drain_single(ByteProducer::new(bytes));
+            assert_eq!(single, bytes);
+            for chunk in 1..=8 {
+                assert_eq!(drain_many(ByteProducer::new(bytes), chunk), single);
+                assert_eq!(drain_to(ByteProducer::new(bytes), chunk), single);
+            }
+        }
+    }
+
+    #[test]
+    fn produce_many_copies_as_much_as_fits() {
+        let mut producer = ByteProducer::new(&BYTES);
+        let mut buf = [MaybeUninit::uninit(); 5];
+        assert_eq!(
+            producer.produce_many(&mut buf).map(NonZeroUsize::get),
+            Ok(5)
+        );
+        assert_eq!(producer.remaining_slice(), [6, 7]);
+        assert_eq!(
+            producer.produce_many(&mut buf).map(NonZeroUsize::get),
+            Ok(2)
+        );
+        assert_eq!(producer.produce_many(&mut buf), Err(()));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::producer::{Producer, SizedProducer};
+use crate::Either;
+
+/// Produces the items of a first producer, and after its internal state change the items of a
+/// second producer.
+///
+/// The internal state change of the first producer is not reported. `slurp` and `stop` are
+/// forwarded to whichever producer is currently active.
+#[derive(Debug, Clone)]
+pub struct ChainProducer<A, B> {
+    a: A,
+    b: B,
+    on_b: bool,
+}
+
+impl<A, B> ChainProducer<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        ChainProducer { a, b, on_b: false }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B> Producer for ChainProducer<A, B>
+where
+    A: Producer,
+    B: Producer<Item = A::Item, Ex = A::Ex>,
+{
+    type Item = A::Item;
+    type Ex = A::Ex;
+    type In = Either<A::In, B::In>;
+
+    fn produce(&mut self) -> Result<A::Item, Self::In> {
+        if !self.on_b {
+            match self.a.produce() {
+                Ok(item) => return Ok(item),
+                Err(_) => self.on_b = true,
+            }
+        }
+        self.b.produce().map_err(Either::Right)
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        if self.on_b {
+            self.b.slurp().map_err(Either::Right)
+        } else {
+            self.a.slurp().map_err(Either::Left)
+        }
+    }
+
+    fn stop(&mut self, reason: A::Ex) -> Result<(), Self::In> {
+        if self.on_b {
+            self.b.stop(reason).map_err(Either::Right)
+        } else {
+            self.a.stop(reason).map_err(Either::Left)
+        }
+    }
+}
+
+impl<A, B> SizedProducer for ChainProducer<A, B>
+where
+    A: SizedProducer,
+    B: SizedProducer<Item = A::Item, Ex = A::Ex>,
+{
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower_b, upper_b) = self.b.size_hint();
+        if self.on_b {
+            return (lower_b, upper_b);
+        }
+
+        let (lower_a, upper_a) = self.a.size_hint();
+        let upper = match (upper_a, upper_b) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (lower_a.saturating_add(lower_b), upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    #[test]
+    fn switches_to_second_after_first_state_change() {
+        let a = ScriptedProducer::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::StateChange("a"),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let b = ScriptedProducer::new([ProduceOutcome::Yield(2), ProduceOutcome::StateChange("b")]);
+        let mut producer = ChainProducer::new(a, b);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err(Either::Right("b")));
+        assert_eq!(producer.stop(()), Ok(()));
+
+        let (a, b) = producer.into_inner();
+        assert_eq!(a.log(), [ProducerCall::Produce, ProducerCall::Produce]);
+        assert_eq!(
+            b.log(),
+            [
+                ProducerCall::Produce,
+                ProducerCall::Produce,
+                ProducerCall::Stop(())
+            ]
+        );
+    }
+
+    #[test]
+    fn slurp_goes_to_active_producer() {
+        let a =
+            ScriptedProducer::<u8, _>::new([ProduceOutcome::StateChange(())]).with_slurp([Err(())]);
+        let b = ScriptedProducer::<u8, ()>::new([]);
+        let mut producer = ChainProducer::new(a, b);
+
+        assert_eq!(producer.slurp(), Err(Either::Left(())));
+        let (a, b) = producer.into_inner();
+        assert_eq!(a.log(), [ProducerCall::Slurp]);
+        assert!(b.log().is_empty());
+    }
+}
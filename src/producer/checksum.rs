@@ -0,0 +1,111 @@
+use core::hash::Hasher;
+
+use crate::producer::Producer;
+
+const CHECKSUM_LEN: usize = 8;
+
+/// The internal state change of a [`ChecksumVerifyingProducer`], emitted once the wrapped
+/// producer performs an internal state change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChecksumVerifyError<I> {
+    /// The last eight bytes matched the checksum of all bytes before them.
+    Verified(I),
+    /// The last eight bytes did not match the checksum of all bytes before them.
+    Mismatch {
+        expected: u64,
+        computed: u64,
+        inner: I,
+    },
+    /// The wrapped producer produced fewer than eight bytes.
+    Truncated(I),
+}
+
+/// Produces the bytes of the wrapped producer except for the last eight, which are checked against
+/// the result of a [`Hasher`] fed with all other bytes.
+///
+/// This reads back the output of a [`ChecksumConsumer`](crate::consumer::ChecksumConsumer): the
+/// checksum is expected as eight big-endian bytes, and the hasher is fed with [`Hasher::write`]
+/// one byte at a time. To tell the checksum apart from the payload, the producer stays eight
+/// bytes ahead of the items it produces. Once the wrapped producer performs an internal state
+/// change, it is reported together with the verification result.
+#[derive(Debug, Clone)]
+pub struct ChecksumVerifyingProducer<P, H> {
+    producer: P,
+    hasher: H,
+    window: [u8; CHECKSUM_LEN],
+    held: usize,
+}
+
+impl<P, H: Hasher> ChecksumVerifyingProducer<P, H> {
+    pub fn new(producer: P, hasher: H) -> Self {
+        ChecksumVerifyingProducer {
+            producer,
+            hasher,
+            window: [0; CHECKSUM_LEN],
+            held: 0,
+        }
+    }
+
+    /// Returns the wrapped producer and the hasher. Bytes held back as a potential checksum are
+    /// lost.
+    pub fn into_inner(self) -> (P, H) {
+        (self.producer, self.hasher)
+    }
+
+    fn verify<I>(&self, inner: I) -> ChecksumVerifyError<I> {
+        if self.held < CHECKSUM_LEN {
+            return ChecksumVerifyError::Truncated(inner);
+        }
+
+        let expected = u64::from_be_bytes(self.window);
+        let computed = self.hasher.finish();
+        if expected == computed {
+            ChecksumVerifyError::Verified(inner)
+        } else {
+            ChecksumVerifyError::Mismatch {
+                expected,
+                computed,
+                inner,
+            }
+        }
+    }
+}
+
+impl<P: Producer<Item = u8>, H: Hasher> Producer for ChecksumVerifyingProducer<P, H> {
+    type Item = u8;
+    type Ex = P::Ex;
+    type In = ChecksumVerifyError<P::In>;
+
+    fn produce(&mut self) -> Result<u8, Self::In> {
+        while self.held < CHECKSUM_LEN {
+            match self.producer.produce() {
+                Ok(byte) => {
+                    self.window[self.held] = byte;
+                    self.held += 1;
+                }
+                Err(inner) => return Err(self.verify(inner)),
+            }
+        }
+
+        match self.producer.produce() {
+            Ok(byte) => {
+                let item = self.window[0];
+                self.window.copy_within(1.., 0);
+                self.window[CHECKSUM_LEN - 1] = byte;
+                self.hasher.write(&[item]);
+                Ok(item)
+            }
+            Err(inner) => Err(self.verify(inner)),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(|inner| self.verify(inner))
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.producer
+            .stop(reason)
+            .map_err(|inner| self.verify(inner))
+    }
+}
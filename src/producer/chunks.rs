@@ -0,0 +1,191 @@
+use core::array;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::producer::Producer;
+
+/// Produces the items of the wrapped producer in non-overlapping chunks of `N` items.
+///
+/// If the wrapped producer performs an internal state change before a chunk is complete, the
+/// items of the partial chunk are dropped and the internal state change is passed on. `slurp`
+/// slurps the wrapped producer and then fills the current chunk. `stop` drops the items of the
+/// current chunk and stops the wrapped producer.
+pub struct ChunksProducer<P: Producer, const N: usize> {
+    producer: P,
+    // The first `len` items are initialized.
+    buf: [MaybeUninit<P::Item>; N],
+    len: usize,
+}
+
+impl<P: Producer, const N: usize> ChunksProducer<P, N> {
+    /// Panics if `N` is zero.
+    pub fn new(producer: P) -> Self {
+        assert!(N > 0, "ChunksProducer requires a nonzero chunk size");
+        ChunksProducer {
+            producer,
+            buf: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the items of the current, incomplete chunk.
+    pub fn partial(&self) -> &[P::Item] {
+        let partial = &self.buf[..self.len];
+        unsafe { &*(partial as *const [MaybeUninit<P::Item>] as *const [P::Item]) }
+    }
+
+    // Produces items until the chunk is complete, dropping the partial chunk on an internal state
+    // change.
+    fn fill(&mut self) -> Result<(), P::In> {
+        while self.len < N {
+            match self.producer.produce() {
+                Ok(item) => {
+                    self.buf[self.len] = MaybeUninit::new(item);
+                    self.len += 1;
+                }
+                Err(e) => {
+                    self.drop_partial();
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn drop_partial(&mut self) {
+        let partial = &mut self.buf[..self.len];
+        self.len = 0;
+        unsafe { ptr::drop_in_place(partial as *mut [MaybeUninit<P::Item>] as *mut [P::Item]) };
+    }
+}
+
+impl<P: Producer, const N: usize> Producer for ChunksProducer<P, N> {
+    type Item = [P::Item; N];
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<[P::Item; N], P::In> {
+        self.fill()?;
+        self.len = 0;
+        Ok(array::from_fn(|i| unsafe {
+            self.buf[i].assume_init_read()
+        }))
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        if let Err(e) = self.producer.slurp() {
+            self.drop_partial();
+            return Err(e);
+        }
+        self.fill()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.drop_partial();
+        self.producer.stop(reason)
+    }
+}
+
+impl<P: Producer, const N: usize> Drop for ChunksProducer<P, N> {
+    fn drop(&mut self) {
+        self.drop_partial();
+    }
+}
+
+impl<P, const N: usize> Clone for ChunksProducer<P, N>
+where
+    P: Producer + Clone,
+    P::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut clone = ChunksProducer {
+            producer: self.producer.clone(),
+            buf: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        };
+        for item in self.partial() {
+            clone.buf[clone.len] = MaybeUninit::new(item.clone());
+            clone.len += 1;
+        }
+        clone
+    }
+}
+
+impl<P, const N: usize> fmt::Debug for ChunksProducer<P, N>
+where
+    P: Producer + fmt::Debug,
+    P::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksProducer")
+            .field("producer", &self.producer)
+            .field("partial", &self.partial())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::producer::ArrayProducer;
+    use crate::testing::{DropCounted, ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn exact_multiple() {
+        let mut producer = ChunksProducer::<_, 2>::new(&[1, 2, 3, 4][..]);
+        assert_eq!(producer.produce(), Ok([1, 2]));
+        assert_eq!(producer.produce(), Ok([3, 4]));
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn trailing_partial_chunk_is_dropped() {
+        let drops = Cell::new(0);
+        let items: [_; 5] = core::array::from_fn(|i| DropCounted::new(i, &drops));
+        let mut producer = ChunksProducer::<_, 2>::new(ArrayProducer::new(items));
+
+        let chunk = producer.produce().unwrap();
+        assert_eq!([chunk[0].value, chunk[1].value], [0, 1]);
+        assert!(producer.produce().is_ok());
+        assert_eq!(drops.get(), 2);
+        assert!(producer.produce().is_err());
+        assert!(producer.partial().is_empty());
+        assert_eq!(drops.get(), 3);
+
+        drop(producer);
+        drop(chunk);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn state_change_mid_chunk() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::StateChange("inner"),
+            ProduceOutcome::Yield(4),
+            ProduceOutcome::Yield(5),
+            ProduceOutcome::Yield(6),
+        ]);
+        let mut producer = ChunksProducer::<_, 3>::new(inner);
+        assert_eq!(producer.produce(), Ok([1, 2, 3]));
+        assert_eq!(producer.produce(), Err("inner"));
+        assert_eq!(producer.produce(), Ok([4, 5, 6]));
+    }
+
+    #[test]
+    fn slurp_fills_chunk_and_drop_drops_it() {
+        let drops = Cell::new(0);
+        let items: [_; 3] = core::array::from_fn(|i| DropCounted::new(i, &drops));
+        let mut producer = ChunksProducer::<_, 2>::new(ArrayProducer::new(items));
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.partial().len(), 2);
+        drop(producer);
+        assert_eq!(drops.get(), 3);
+    }
+}
@@ -0,0 +1,101 @@
+use core::convert::Infallible;
+
+use crate::producer::Producer;
+
+/// Produces the items of the wrapped producer, replacing it with a clone of the original whenever
+/// it performs an internal state change, forever.
+///
+/// The clone is taken before the first item is produced, so it includes all internal state the
+/// producer had at that point. `produce` panics if a fresh clone performs an internal state change
+/// right away, since the cycle would never produce an item. `stop` stops the current producer,
+/// ignoring its internal state change, and drops the original. Calling `produce` after that
+/// panics.
+#[derive(Debug, Clone)]
+pub struct CycleProducer<P> {
+    current: P,
+    original: Option<P>,
+}
+
+impl<P: Clone> CycleProducer<P> {
+    pub fn new(producer: P) -> Self {
+        CycleProducer {
+            original: Some(producer.clone()),
+            current: producer,
+        }
+    }
+}
+
+impl<P: Producer + Clone> Producer for CycleProducer<P> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = Infallible;
+
+    fn produce(&mut self) -> Result<P::Item, Infallible> {
+        if let Ok(item) = self.current.produce() {
+            return Ok(item);
+        }
+
+        self.current = self
+            .original
+            .clone()
+            .expect("called produce on a stopped CycleProducer");
+        match self.current.produce() {
+            Ok(item) => Ok(item),
+            Err(_) => panic!("CycleProducer over a producer without items"),
+        }
+    }
+
+    /// Slurps the current producer, ignoring its internal state change.
+    fn slurp(&mut self) -> Result<(), Infallible> {
+        let _ = self.current.slurp();
+        Ok(())
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Infallible> {
+        self.original = None;
+        let _ = self.current.stop(reason);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::EmptyProducer;
+    use crate::ProducerExt;
+
+    #[test]
+    fn restarts_from_original() {
+        let mut producer = (&[1, 2, 3][..]).cycle();
+        for expected in [1, 2, 3, 1, 2, 3, 1] {
+            assert_eq!(producer.produce(), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn clone_includes_internal_state() {
+        let mut inner = &[1, 2, 3][..];
+        assert_eq!(inner.produce(), Ok(1));
+
+        let mut producer = inner.cycle();
+        for expected in [2, 3, 2, 3, 2] {
+            assert_eq!(producer.produce(), Ok(expected));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "CycleProducer over a producer without items")]
+    fn panics_without_items() {
+        let mut producer = EmptyProducer::<u8>::new().cycle();
+        let _ = producer.produce();
+    }
+
+    #[test]
+    #[should_panic(expected = "called produce on a stopped CycleProducer")]
+    fn panics_after_stop() {
+        let mut producer = Some(1).cycle();
+        assert_eq!(producer.stop(()), Ok(()));
+        let _ = producer.produce();
+    }
+}
@@ -0,0 +1,52 @@
+use crate::producer::Producer;
+
+/// Skips items of the wrapped producer that are equal to the item produced directly before them.
+///
+/// The first item is always produced. Internal state changes of the wrapped producer are passed
+/// on immediately, the last produced item is remembered across them.
+#[derive(Debug, Clone)]
+pub struct DedupProducer<P: Producer> {
+    producer: P,
+    last: Option<P::Item>,
+}
+
+impl<P: Producer> DedupProducer<P> {
+    pub fn new(producer: P) -> Self {
+        DedupProducer {
+            producer,
+            last: None,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P> Producer for DedupProducer<P>
+where
+    P: Producer,
+    P::Item: PartialEq + Clone,
+{
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        loop {
+            let item = self.producer.produce()?;
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                return Ok(item);
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
@@ -0,0 +1,70 @@
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+
+use crate::producer::Producer;
+use crate::Either;
+
+/// One of two producers with the same item and state change types, selected at runtime.
+///
+/// All method calls are forwarded to the selected producer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EitherProducer<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> EitherProducer<L, R> {
+    pub fn into_inner(self) -> Either<L, R> {
+        match self {
+            EitherProducer::Left(l) => Either::Left(l),
+            EitherProducer::Right(r) => Either::Right(r),
+        }
+    }
+}
+
+impl<L, R> From<Either<L, R>> for EitherProducer<L, R> {
+    fn from(either: Either<L, R>) -> Self {
+        match either {
+            Either::Left(l) => EitherProducer::Left(l),
+            Either::Right(r) => EitherProducer::Right(r),
+        }
+    }
+}
+
+impl<L, R> Producer for EitherProducer<L, R>
+where
+    L: Producer,
+    R: Producer<Item = L::Item, Ex = L::Ex, In = L::In>,
+{
+    type Item = L::Item;
+    type Ex = L::Ex;
+    type In = L::In;
+
+    fn produce(&mut self) -> Result<L::Item, L::In> {
+        match self {
+            EitherProducer::Left(l) => l.produce(),
+            EitherProducer::Right(r) => r.produce(),
+        }
+    }
+
+    fn produce_many(&mut self, buf: &mut [MaybeUninit<L::Item>]) -> Result<NonZeroUsize, L::In> {
+        match self {
+            EitherProducer::Left(l) => l.produce_many(buf),
+            EitherProducer::Right(r) => r.produce_many(buf),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), L::In> {
+        match self {
+            EitherProducer::Left(l) => l.slurp(),
+            EitherProducer::Right(r) => r.slurp(),
+        }
+    }
+
+    fn stop(&mut self, reason: L::Ex) -> Result<(), L::In> {
+        match self {
+            EitherProducer::Left(l) => l.stop(reason),
+            EitherProducer::Right(r) => r.stop(reason),
+        }
+    }
+}
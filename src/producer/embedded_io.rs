@@ -0,0 +1,111 @@
+use embedded_io::{Error, ErrorKind, Read};
+
+use crate::producer::Producer;
+
+/// The internal state change of an [`EmbeddedIoReadProducer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmbeddedIoReadError<E> {
+    /// The reader has no more bytes.
+    Eof,
+    /// The reader returned an error.
+    Io(E),
+}
+
+/// Produces the bytes of an [`embedded_io::Read`], reading them into an internal buffer of `N`
+/// bytes in chunks.
+///
+/// A read of zero bytes is interpreted as the end of the reader, reads that are interrupted are
+/// retried. After `stop` (which discards the buffered bytes), `produce` keeps returning
+/// `Err(Eof)`, but the reader can still be recovered with `into_inner`.
+#[derive(Debug, Clone)]
+pub struct EmbeddedIoReadProducer<R, const N: usize> {
+    reader: R,
+    buf: [u8; N],
+    pos: usize,
+    filled: usize,
+    eof: bool,
+}
+
+impl<R: Read, const N: usize> EmbeddedIoReadProducer<R, N> {
+    /// Panics if `N` is zero.
+    pub fn new(reader: R) -> Self {
+        assert!(N > 0, "EmbeddedIoReadProducer requires a nonzero capacity");
+        EmbeddedIoReadProducer {
+            reader,
+            buf: [0; N],
+            pos: 0,
+            filled: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the reader. Buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Performs a single read into the free part of the buffer, returning how many bytes were read.
+    fn fill(&mut self) -> Result<usize, EmbeddedIoReadError<R::Error>> {
+        loop {
+            match self.reader.read(&mut self.buf[self.filled..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    return Ok(0);
+                }
+                Ok(read) => {
+                    self.filled += read;
+                    return Ok(read);
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(EmbeddedIoReadError::Io(e)),
+            }
+        }
+    }
+}
+
+impl<R: Read, const N: usize> Producer for EmbeddedIoReadProducer<R, N> {
+    type Item = u8;
+    type Ex = ();
+    type In = EmbeddedIoReadError<R::Error>;
+
+    fn produce(&mut self) -> Result<u8, Self::In> {
+        if self.pos == self.filled {
+            if self.eof {
+                return Err(EmbeddedIoReadError::Eof);
+            }
+
+            self.pos = 0;
+            self.filled = 0;
+            if self.fill()? == 0 {
+                return Err(EmbeddedIoReadError::Eof);
+            }
+        }
+
+        let byte = self.buf[self.pos];
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    /// Performs a single read into the buffer, unless the buffer is full or the end of the reader
+    /// has been reached.
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        if self.eof {
+            return Ok(());
+        }
+
+        self.buf.copy_within(self.pos..self.filled, 0);
+        self.filled -= self.pos;
+        self.pos = 0;
+        if self.filled < N {
+            self.fill()?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), Self::In> {
+        self.pos = 0;
+        self.filled = 0;
+        self.eof = true;
+        Ok(())
+    }
+}
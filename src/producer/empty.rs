@@ -0,0 +1,95 @@
+use core::convert::Infallible;
+use core::fmt;
+use core::iter::Empty;
+use core::marker::PhantomData;
+
+use crate::producer::{Producer, SizedProducer};
+
+/// Produces nothing, every call to `produce` or `slurp` performs an internal state change with
+/// `In::default()`.
+///
+/// There is nothing to stop, so `Ex` is [`Infallible`]. The producer keeps behaving the same after
+/// an internal state change.
+pub struct EmptyProducer<T, In = ()> {
+    _item: PhantomData<fn() -> (T, In)>,
+}
+
+impl<T, In> EmptyProducer<T, In> {
+    pub fn new() -> Self {
+        EmptyProducer { _item: PhantomData }
+    }
+}
+
+/// Creates a producer that produces nothing.
+pub fn empty<T, In: Default>() -> EmptyProducer<T, In> {
+    EmptyProducer::new()
+}
+
+impl<T, In: Default> Producer for EmptyProducer<T, In> {
+    type Item = T;
+    type Ex = Infallible;
+    type In = In;
+
+    fn produce(&mut self) -> Result<T, In> {
+        Err(In::default())
+    }
+
+    fn slurp(&mut self) -> Result<(), In> {
+        Err(In::default())
+    }
+
+    fn stop(&mut self, reason: Infallible) -> Result<(), In> {
+        match reason {}
+    }
+}
+
+impl<T, In: Default> SizedProducer for EmptyProducer<T, In> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+/// `Empty` does not implement [`Producer`] itself, since the methods of
+/// [`ProducerExt`](crate::ProducerExt) would be ambiguous with those of `Iterator`.
+impl<T, In> From<Empty<T>> for EmptyProducer<T, In> {
+    fn from(_iter: Empty<T>) -> Self {
+        EmptyProducer::new()
+    }
+}
+
+impl<T, In> Default for EmptyProducer<T, In> {
+    fn default() -> Self {
+        EmptyProducer::new()
+    }
+}
+
+impl<T, In> Clone for EmptyProducer<T, In> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, In> Copy for EmptyProducer<T, In> {}
+
+impl<T, In> fmt::Debug for EmptyProducer<T, In> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EmptyProducer")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_sequence() {
+        let mut producer = EmptyProducer::<u8>::from(core::iter::empty());
+        assert_eq!(producer.size_hint(), (0, Some(0)));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.slurp(), Err(()));
+        assert_eq!(producer.produce(), Err(()));
+
+        let mut producer = empty::<u8, &str>();
+        assert_eq!(producer.produce(), Err(""));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::producer::Producer;
+
+/// Pairs every item of the wrapped producer with its index.
+///
+/// The index saturates at `usize::MAX`.
+#[derive(Debug, Clone)]
+pub struct EnumerateProducer<P> {
+    producer: P,
+    count: usize,
+}
+
+impl<P> EnumerateProducer<P> {
+    pub fn new(producer: P) -> Self {
+        EnumerateProducer { producer, count: 0 }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer> Producer for EnumerateProducer<P> {
+    type Item = (usize, P::Item);
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<(usize, P::Item), P::In> {
+        let item = self.producer.produce()?;
+        let index = self.count;
+        self.count = self.count.saturating_add(1);
+        Ok((index, item))
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn state_change_does_not_advance_index() {
+        let inner = ScriptedProducer::<char, (), ()>::new([
+            ProduceOutcome::Yield('a'),
+            ProduceOutcome::StateChange(()),
+            ProduceOutcome::StateChange(()),
+            ProduceOutcome::Yield('b'),
+        ]);
+        let mut producer = EnumerateProducer::new(inner);
+        assert_eq!(producer.produce(), Ok((0, 'a')));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.produce(), Ok((1, 'b')));
+    }
+
+    #[test]
+    fn index_saturates() {
+        let inner = ScriptedProducer::<char, (), ()>::new([
+            ProduceOutcome::Yield('a'),
+            ProduceOutcome::Yield('b'),
+            ProduceOutcome::Yield('c'),
+        ]);
+        let mut producer = EnumerateProducer {
+            producer: inner,
+            count: usize::MAX - 1,
+        };
+        assert_eq!(producer.produce(), Ok((usize::MAX - 1, 'a')));
+        assert_eq!(producer.produce(), Ok((usize::MAX, 'b')));
+        assert_eq!(producer.produce(), Ok((usize::MAX, 'c')));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::producer::{
+    ChainProducer, ChunksProducer, CycleProducer, DedupProducer, EnumerateProducer,
+    FallbackProducer, FilterProducer, FlatMapProducer, FlattenProducer, InspectProducer,
+    MapErrProducer, MapProducer, PeekableProducer, Producer, ProducerIter, ScanProducer,
+    SkipProducer, TakeProducer, ZipProducer,
+};
+
+/// Provided methods for all producers.
+///
+/// These are kept out of [`Producer`] so that the core trait stays small and object safe.
+pub trait ProducerExt: Producer {
+    /// Turns the producer into an iterator over its items.
+    fn produce_into_iter(self) -> ProducerIter<Self>
+    where
+        Self: Sized,
+        Self::Ex: Default,
+    {
+        ProducerIter::new(self)
+    }
+
+    /// Produces an item, or returns `Self::Item::default()` if the producer performs an internal
+    /// state change.
+    fn produce_or_default(&mut self) -> Self::Item
+    where
+        Self::Item: Default,
+    {
+        self.produce().unwrap_or_default()
+    }
+
+    /// Applies a function to every produced item.
+    fn map<B, F: FnMut(Self::Item) -> B>(self, f: F) -> MapProducer<Self, F>
+    where
+        Self: Sized,
+    {
+        MapProducer::new(self, f)
+    }
+
+    /// Applies a function to every internal state change.
+    fn map_err<E, F: FnMut(Self::In) -> E>(self, f: F) -> MapErrProducer<Self, F>
+    where
+        Self: Sized,
+    {
+        MapErrProducer::new(self, f)
+    }
+
+    /// Drops all items that do not satisfy the predicate.
+    fn filter<F: FnMut(&Self::Item) -> bool>(self, predicate: F) -> FilterProducer<Self, F>
+    where
+        Self: Sized,
+    {
+        FilterProducer::new(self, predicate)
+    }
+
+    /// Produces at most `n` items, then signals `Err(Self::In::default())`.
+    fn take(self, n: usize) -> TakeProducer<Self>
+    where
+        Self: Sized,
+        Self::In: Default,
+    {
+        TakeProducer::new(self, n)
+    }
+
+    /// Drops the first `n` items.
+    fn skip(self, n: usize) -> SkipProducer<Self>
+    where
+        Self: Sized,
+    {
+        SkipProducer::new(self, n)
+    }
+
+    /// Produces the items of `other` after the internal state change of `self`.
+    fn chain<B>(self, other: B) -> ChainProducer<Self, B>
+    where
+        Self: Sized,
+        B: Producer<Item = Self::Item, Ex = Self::Ex>,
+    {
+        ChainProducer::new(self, other)
+    }
+
+    /// Produces the items of `other` after any internal state change of `self`, reporting only
+    /// the internal state changes of `other`.
+    fn fallback<B>(self, other: B) -> FallbackProducer<Self, B>
+    where
+        Self: Sized,
+        B: Producer<Item = Self::Item>,
+    {
+        FallbackProducer::new(self, other)
+    }
+
+    /// Produces pairs of the items of `self` and `other`.
+    fn zip<B: Producer>(self, other: B) -> ZipProducer<Self, B>
+    where
+        Self: Sized,
+    {
+        ZipProducer::new(self, other)
+    }
+
+    /// Pairs every item with its index.
+    fn enumerate(self) -> EnumerateProducer<Self>
+    where
+        Self: Sized,
+    {
+        EnumerateProducer::new(self)
+    }
+
+    /// Allows looking at the next item without producing it.
+    fn peekable(self) -> PeekableProducer<Self>
+    where
+        Self: Sized,
+    {
+        PeekableProducer::new(self)
+    }
+
+    /// Calls a function with a reference to every item before producing it.
+    fn inspect<F: FnMut(&Self::Item)>(self, f: F) -> InspectProducer<Self, F>
+    where
+        Self: Sized,
+    {
+        InspectProducer::new(self, f)
+    }
+
+    /// Maps every item to a producer and produces the items of those producers, moving on to the
+    /// next one whenever `is_end` returns `true` for an internal state change.
+    fn flat_map<Q, F, E>(self, f: F, is_end: E) -> FlatMapProducer<Self, F, E, Q>
+    where
+        Self: Sized,
+        Q: Producer,
+        F: FnMut(Self::Item) -> Q,
+        E: FnMut(&Q::In) -> bool,
+    {
+        FlatMapProducer::new(self, f, is_end)
+    }
+
+    /// Produces the items of the producers that this producer produces, moving on to the next one
+    /// whenever `is_end` returns `true` for an internal state change.
+    fn flatten<E>(self, is_end: E) -> FlattenProducer<Self, E>
+    where
+        Self: Sized,
+        Self::Item: Producer,
+        E: FnMut(&<Self::Item as Producer>::In) -> bool,
+    {
+        FlattenProducer::new(self, is_end)
+    }
+
+    /// Maps every item with a closure that has access to a mutable state, until the closure
+    /// returns `None`.
+    fn scan<S, B, F>(self, initial_state: S, f: F) -> ScanProducer<Self, S, F>
+    where
+        Self: Sized,
+        Self::In: Default,
+        F: FnMut(&mut S, Self::Item) -> Option<B>,
+    {
+        ScanProducer::new(self, initial_state, f)
+    }
+
+    /// Skips items that are equal to the item produced directly before them.
+    fn dedup(self) -> DedupProducer<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq + Clone,
+    {
+        DedupProducer::new(self)
+    }
+
+    /// Restarts from a clone of the original producer whenever it performs an internal state
+    /// change, forever.
+    fn cycle(self) -> CycleProducer<Self>
+    where
+        Self: Sized + Clone,
+    {
+        CycleProducer::new(self)
+    }
+
+    /// Produces the items in non-overlapping chunks of `N` items.
+    fn chunks<const N: usize>(self) -> ChunksProducer<Self, N>
+    where
+        Self: Sized,
+    {
+        ChunksProducer::new(self)
+    }
+}
+
+impl<P: Producer + ?Sized> ProducerExt for P {}
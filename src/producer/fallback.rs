@@ -0,0 +1,132 @@
+use crate::producer::Producer;
+use crate::Either;
+
+/// Produces the items of a primary producer, and after any internal state change of it the items
+/// of a secondary producer.
+///
+/// Internal state changes of the primary producer are never reported, they only cause the switch
+/// to the secondary one. This also applies to `slurp`, which slurps the secondary producer if
+/// slurping the primary one fails. `stop` is forwarded to the producer the reason belongs to: a
+/// `Left` reason stops the primary producer unless it has already been left behind, a `Right`
+/// reason always stops the secondary producer.
+#[derive(Debug, Clone)]
+pub struct FallbackProducer<A, B> {
+    a: A,
+    b: B,
+    on_b: bool,
+}
+
+impl<A, B> FallbackProducer<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        FallbackProducer { a, b, on_b: false }
+    }
+
+    /// Returns whether the secondary producer is active.
+    pub fn is_fallback(&self) -> bool {
+        self.on_b
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B> Producer for FallbackProducer<A, B>
+where
+    A: Producer,
+    B: Producer<Item = A::Item>,
+{
+    type Item = A::Item;
+    type Ex = Either<A::Ex, B::Ex>;
+    type In = B::In;
+
+    fn produce(&mut self) -> Result<A::Item, B::In> {
+        if !self.on_b {
+            match self.a.produce() {
+                Ok(item) => return Ok(item),
+                Err(_) => self.on_b = true,
+            }
+        }
+        self.b.produce()
+    }
+
+    fn slurp(&mut self) -> Result<(), B::In> {
+        if !self.on_b {
+            match self.a.slurp() {
+                Ok(()) => return Ok(()),
+                Err(_) => self.on_b = true,
+            }
+        }
+        self.b.slurp()
+    }
+
+    fn stop(&mut self, reason: Self::Ex) -> Result<(), B::In> {
+        match reason {
+            Either::Left(reason) => {
+                if !self.on_b {
+                    let _ = self.a.stop(reason);
+                }
+                Ok(())
+            }
+            Either::Right(reason) => self.b.stop(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    fn primary() -> ScriptedProducer<u8, &'static str, &'static str> {
+        ScriptedProducer::new([ProduceOutcome::Yield(1), ProduceOutcome::StateChange("a")])
+    }
+
+    fn secondary() -> ScriptedProducer<u8, &'static str, &'static str> {
+        ScriptedProducer::new([ProduceOutcome::Yield(2), ProduceOutcome::StateChange("b")])
+    }
+
+    #[test]
+    fn switches_on_primary_state_change() {
+        let mut producer = FallbackProducer::new(primary(), secondary());
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert!(!producer.is_fallback());
+        assert_eq!(producer.produce(), Ok(2));
+        assert!(producer.is_fallback());
+        assert_eq!(producer.produce(), Err("b"));
+    }
+
+    #[test]
+    fn stop_left_before_switch_stops_primary() {
+        let mut producer = FallbackProducer::new(primary(), secondary());
+
+        assert_eq!(producer.stop(Either::Left("stop")), Ok(()));
+        let (a, b) = producer.into_inner();
+        assert_eq!(a.log(), &[ProducerCall::Stop("stop")]);
+        assert!(b.log().is_empty());
+    }
+
+    #[test]
+    fn stop_left_after_switch_is_ignored() {
+        let mut producer = FallbackProducer::new(primary(), secondary());
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.stop(Either::Left("stop")), Ok(()));
+        let (a, b) = producer.into_inner();
+        assert_eq!(a.log(), &[ProducerCall::Produce, ProducerCall::Produce]);
+        assert_eq!(b.log(), &[ProducerCall::Produce]);
+    }
+
+    #[test]
+    fn stop_right_stops_secondary() {
+        let mut producer = FallbackProducer::new(primary(), secondary());
+
+        assert_eq!(producer.stop(Either::Right("stop")), Ok(()));
+        let (a, b) = producer.into_inner();
+        assert!(a.log().is_empty());
+        assert_eq!(b.log(), &[ProducerCall::Stop("stop")]);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::producer::Producer;
+
+/// Produces only those items of the wrapped producer that satisfy a predicate.
+///
+/// Items that do not satisfy the predicate are dropped.
+#[derive(Debug, Clone)]
+pub struct FilterProducer<P, F> {
+    producer: P,
+    predicate: F,
+}
+
+impl<P, F> FilterProducer<P, F> {
+    pub fn new(producer: P, predicate: F) -> Self {
+        FilterProducer {
+            producer,
+            predicate,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer, F: FnMut(&P::Item) -> bool> Producer for FilterProducer<P, F> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        loop {
+            let item = self.producer.produce()?;
+            if (self.predicate)(&item) {
+                return Ok(item);
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    #[test]
+    fn skips_rejected_items() {
+        let mut producer =
+            FilterProducer::new(SliceProducer::new(&[1, 2, 3, 4, 5]), |x: &u8| x % 2 == 1);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Ok(5));
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    #[test]
+    fn state_change_while_skipping() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::StateChange("in"),
+            ProduceOutcome::Yield(4),
+            ProduceOutcome::Yield(5),
+        ]);
+        let mut producer = FilterProducer::new(inner, |x: &u8| x % 2 == 1);
+
+        assert_eq!(producer.produce(), Err("in"));
+        assert_eq!(producer.produce(), Ok(5));
+    }
+
+    #[test]
+    fn forwards_slurp_and_stop() {
+        let inner = ScriptedProducer::<u8, (), &str>::new([]);
+        let mut producer = FilterProducer::new(inner, |_: &u8| true);
+
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.stop("done"), Ok(()));
+        assert_eq!(
+            producer.into_inner().log(),
+            &[ProducerCall::Slurp, ProducerCall::Stop("done")]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::producer::Producer;
+use crate::Either;
+
+/// Maps every item of the wrapped producer to a producer, and produces the items of those
+/// producers in order.
+///
+/// When an inner producer performs an internal state change for which `is_end` returns `true`,
+/// the next item of the outer producer is mapped instead. All other internal state changes of the
+/// inner producer are reported as `Either::Right`, those of the outer producer as `Either::Left`.
+/// `slurp` is forwarded to whichever producer is currently active. `stop` stops the active inner
+/// producer (if any) and the outer producer, even if stopping the inner one fails.
+#[derive(Debug, Clone)]
+pub struct FlatMapProducer<P, F, E, Q> {
+    outer: P,
+    f: F,
+    is_end: E,
+    inner: Option<Q>,
+}
+
+impl<P, F, E, Q> FlatMapProducer<P, F, E, Q> {
+    pub fn new(outer: P, f: F, is_end: E) -> Self {
+        FlatMapProducer {
+            outer,
+            f,
+            is_end,
+            inner: None,
+        }
+    }
+
+    /// Returns the outer producer and the active inner producer.
+    pub fn into_inner(self) -> (P, Option<Q>) {
+        (self.outer, self.inner)
+    }
+}
+
+impl<P, F, E, Q> Producer for FlatMapProducer<P, F, E, Q>
+where
+    P: Producer,
+    F: FnMut(P::Item) -> Q,
+    E: FnMut(&Q::In) -> bool,
+    Q: Producer,
+{
+    type Item = Q::Item;
+    type Ex = (P::Ex, Q::Ex);
+    type In = Either<P::In, Q::In>;
+
+    fn produce(&mut self) -> Result<Q::Item, Self::In> {
+        loop {
+            if let Some(inner) = self.inner.as_mut() {
+                match inner.produce() {
+                    Ok(item) => return Ok(item),
+                    Err(e) if (self.is_end)(&e) => self.inner = None,
+                    Err(e) => return Err(Either::Right(e)),
+                }
+            }
+
+            let item = self.outer.produce().map_err(Either::Left)?;
+            self.inner = Some((self.f)(item));
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        match self.inner.as_mut() {
+            Some(inner) => inner.slurp().map_err(Either::Right),
+            None => self.outer.slurp().map_err(Either::Left),
+        }
+    }
+
+    fn stop(&mut self, (reason_outer, reason_inner): Self::Ex) -> Result<(), Self::In> {
+        let inner = match self.inner.as_mut() {
+            Some(inner) => inner.stop(reason_inner).map_err(Either::Right),
+            None => Ok(()),
+        };
+        let outer = self.outer.stop(reason_outer).map_err(Either::Left);
+        inner.and(outer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{unfold, SliceEnd, SliceProducer};
+
+    #[test]
+    fn produces_inner_items_in_order() {
+        let mut producer = FlatMapProducer::new(
+            SliceProducer::new(&[1, 0, 2]),
+            |n: usize| &[10, 20][..n],
+            |_: &()| true,
+        );
+        assert_eq!(producer.produce(), Ok(10));
+        assert_eq!(producer.produce(), Ok(10));
+        assert_eq!(producer.produce(), Ok(20));
+        assert_eq!(producer.produce(), Err(Either::Left(SliceEnd)));
+    }
+
+    #[test]
+    fn reports_inner_errors() {
+        let mut producer = FlatMapProducer::new(
+            SliceProducer::new(&[1, 2]),
+            |n: u8| {
+                unfold(0, move |i: &mut u8| {
+                    *i += 1;
+                    match *i {
+                        1 => Ok(n),
+                        2 if n == 2 => Err("broken"),
+                        _ => Err("end"),
+                    }
+                })
+            },
+            |e: &&str| *e == "end",
+        );
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err(Either::Right("broken")));
+    }
+
+    #[test]
+    fn stop_stops_both() {
+        use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+        let mut producer = FlatMapProducer::new(
+            ScriptedProducer::<u8, (), &str>::new([ProduceOutcome::Yield(1)]),
+            |n: u8| ScriptedProducer::<u8, (), &str>::new([ProduceOutcome::Yield(n)]),
+            |_: &()| true,
+        );
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.stop(("outer", "inner")), Ok(()));
+
+        let (outer, inner) = producer.into_inner();
+        assert_eq!(
+            outer.log(),
+            [ProducerCall::Produce, ProducerCall::Stop("outer")]
+        );
+        assert_eq!(
+            inner.unwrap().log(),
+            [ProducerCall::Produce, ProducerCall::Stop("inner")]
+        );
+    }
+}
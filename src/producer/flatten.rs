@@ -0,0 +1,70 @@
+use crate::producer::{FlatMapProducer, Producer};
+use crate::Either;
+
+/// Produces the items of the producers that the wrapped producer produces, in order.
+///
+/// This is a [`FlatMapProducer`] whose mapping function is the identity, see there for how
+/// internal state changes, `slurp` and `stop` are handled.
+#[derive(Debug, Clone)]
+pub struct FlattenProducer<P: Producer, E> {
+    flat_map: FlatMapProducer<P, Identity<P::Item>, E, P::Item>,
+}
+
+type Identity<T> = fn(T) -> T;
+
+fn identity<T>(t: T) -> T {
+    t
+}
+
+impl<P: Producer, E> FlattenProducer<P, E> {
+    pub fn new(outer: P, is_end: E) -> Self {
+        FlattenProducer {
+            flat_map: FlatMapProducer::new(outer, identity, is_end),
+        }
+    }
+
+    /// Returns the outer producer and the active inner producer.
+    pub fn into_inner(self) -> (P, Option<P::Item>) {
+        self.flat_map.into_inner()
+    }
+}
+
+impl<P, E> Producer for FlattenProducer<P, E>
+where
+    P: Producer,
+    P::Item: Producer,
+    E: FnMut(&<P::Item as Producer>::In) -> bool,
+{
+    type Item = <P::Item as Producer>::Item;
+    type Ex = (P::Ex, <P::Item as Producer>::Ex);
+    type In = Either<P::In, <P::Item as Producer>::In>;
+
+    fn produce(&mut self) -> Result<Self::Item, Self::In> {
+        self.flat_map.produce()
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.flat_map.slurp()
+    }
+
+    fn stop(&mut self, reason: Self::Ex) -> Result<(), Self::In> {
+        self.flat_map.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+
+    #[test]
+    fn produces_inner_items_in_order() {
+        let inners: [&[u8]; 3] = [&[1, 2], &[], &[3]];
+        let mut producer = FlattenProducer::new(SliceProducer::new(&inners), |_: &()| true);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err(Either::Left(SliceEnd)));
+    }
+}
@@ -0,0 +1,364 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::producer::{Endianness, Producer};
+
+/// How the length of a frame is encoded in front of its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthPrefix {
+    U8,
+    U16(Endianness),
+    U32(Endianness),
+    U64(Endianness),
+    /// An unsigned LEB128 varint of at most 64 bits, in its shortest encoding.
+    Varint,
+}
+
+// The maximum number of bytes of an encoded length prefix.
+pub(crate) const MAX_PREFIX_LEN: usize = 10;
+
+impl LengthPrefix {
+    // The width in bytes and the endianness of a fixed-width prefix, or `None` for varints.
+    fn fixed(self) -> Option<(usize, Endianness)> {
+        match self {
+            LengthPrefix::U8 => Some((1, Endianness::Little)),
+            LengthPrefix::U16(endianness) => Some((2, endianness)),
+            LengthPrefix::U32(endianness) => Some((4, endianness)),
+            LengthPrefix::U64(endianness) => Some((8, endianness)),
+            LengthPrefix::Varint => None,
+        }
+    }
+
+    // Encodes `len` into the start of `buf`, returning the number of bytes of the encoding, or
+    // `None` if `len` does not fit into the prefix.
+    pub(crate) fn encode(self, len: u64, buf: &mut [u8; MAX_PREFIX_LEN]) -> Option<usize> {
+        let (width, endianness) = match self.fixed() {
+            Some(fixed) => fixed,
+            None => {
+                let mut rest = len;
+                let mut i = 0;
+                loop {
+                    let byte = (rest & 0x7F) as u8;
+                    rest >>= 7;
+                    if rest == 0 {
+                        buf[i] = byte;
+                        return Some(i + 1);
+                    }
+                    buf[i] = byte | 0x80;
+                    i += 1;
+                }
+            }
+        };
+
+        if width < 8 && len >> (8 * width) != 0 {
+            return None;
+        }
+        match endianness {
+            Endianness::Little => buf[..width].copy_from_slice(&len.to_le_bytes()[..width]),
+            Endianness::Big => buf[..width].copy_from_slice(&len.to_be_bytes()[8 - width..]),
+        }
+        Some(width)
+    }
+
+    // Decodes a prefix from the bytes returned by `read_byte`, which is told whether it reads the
+    // first byte of the prefix.
+    fn decode<I, F>(self, mut read_byte: F) -> Result<u64, FramesError<I>>
+    where
+        F: FnMut(bool) -> Result<u8, FramesError<I>>,
+    {
+        let (width, endianness) = match self.fixed() {
+            Some(fixed) => fixed,
+            None => {
+                let mut len = 0;
+                for i in 0..MAX_PREFIX_LEN {
+                    let byte = read_byte(i == 0)?;
+                    let payload = u64::from(byte & 0x7F);
+                    if i == MAX_PREFIX_LEN - 1 && payload > 1 {
+                        return Err(FramesError::InvalidPrefix);
+                    }
+
+                    len |= payload << (7 * i);
+                    if byte & 0x80 == 0 {
+                        if byte == 0 && i > 0 {
+                            return Err(FramesError::InvalidPrefix);
+                        }
+                        return Ok(len);
+                    }
+                }
+                return Err(FramesError::InvalidPrefix);
+            }
+        };
+
+        let mut len = 0;
+        for i in 0..width {
+            let byte = u64::from(read_byte(i == 0)?);
+            match endianness {
+                Endianness::Little => len |= byte << (8 * i),
+                Endianness::Big => len = (len << 8) | byte,
+            }
+        }
+        Ok(len)
+    }
+}
+
+/// The internal state change of a [`FramesProducer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FramesError<I> {
+    /// The wrapped producer performed an internal state change between two frames.
+    Inner(I),
+    /// The wrapped producer performed an internal state change in the middle of a frame. The bytes
+    /// of the frame that have been read are lost.
+    Truncated(I),
+    /// The length prefix is an invalid varint.
+    InvalidPrefix,
+    /// The declared length exceeds the maximum length. The payload has not been read, it is
+    /// skipped at the start of the next call. An internal state change of the wrapped producer
+    /// while skipping is reported as `Truncated`, and skipping resumes on the call after that.
+    TooLong { len: u64 },
+    /// The declared length exceeds the size of the buffer passed to
+    /// [`produce_into`](FramesProducer::produce_into). The payload has not been read, the next call
+    /// retries the same frame.
+    BufferTooSmall { len: usize },
+}
+
+/// Produces the payloads of length-prefixed frames read from the bytes of the wrapped producer.
+///
+/// With the `alloc` feature, this is a producer of `Vec<u8>`s. Frames can also be read into a
+/// buffer provided by the caller with [`produce_into`](FramesProducer::produce_into).
+#[derive(Debug, Clone)]
+pub struct FramesProducer<P> {
+    producer: P,
+    prefix: LengthPrefix,
+    max_len: usize,
+    // The length of a frame whose prefix has been read but whose payload has not.
+    pending_len: Option<usize>,
+    // The number of bytes of a payload that was too long which still have to be skipped.
+    skip: u64,
+}
+
+impl<P> FramesProducer<P> {
+    pub fn new(producer: P, prefix: LengthPrefix) -> Self {
+        FramesProducer::with_max_len(producer, prefix, usize::MAX)
+    }
+
+    /// Creates a producer that performs an internal state change for all frames longer than
+    /// `max_len`.
+    pub fn with_max_len(producer: P, prefix: LengthPrefix, max_len: usize) -> Self {
+        FramesProducer {
+            producer,
+            prefix,
+            max_len,
+            pending_len: None,
+            skip: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer<Item = u8>> FramesProducer<P> {
+    /// Reads the payload of the next frame into the start of `buf`, returning its length.
+    pub fn produce_into(&mut self, buf: &mut [u8]) -> Result<usize, FramesError<P::In>> {
+        let len = self.read_len()?;
+        if len > buf.len() {
+            self.pending_len = Some(len);
+            return Err(FramesError::BufferTooSmall { len });
+        }
+
+        for byte in &mut buf[..len] {
+            *byte = self.producer.produce().map_err(FramesError::Truncated)?;
+        }
+        Ok(len)
+    }
+
+    fn read_len(&mut self) -> Result<usize, FramesError<P::In>> {
+        while self.skip > 0 {
+            self.producer.produce().map_err(FramesError::Truncated)?;
+            self.skip -= 1;
+        }
+
+        if let Some(len) = self.pending_len.take() {
+            return Ok(len);
+        }
+
+        let prefix = self.prefix;
+        let len = prefix.decode(|first| self.read_byte(first))?;
+
+        if len > self.max_len as u64 {
+            self.skip = len;
+            return Err(FramesError::TooLong { len });
+        }
+        Ok(len as usize)
+    }
+
+    fn read_byte(&mut self, first: bool) -> Result<u8, FramesError<P::In>> {
+        self.producer.produce().map_err(|e| {
+            if first {
+                FramesError::Inner(e)
+            } else {
+                FramesError::Truncated(e)
+            }
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Producer<Item = u8>> Producer for FramesProducer<P> {
+    type Item = Vec<u8>;
+    type Ex = P::Ex;
+    type In = FramesError<P::In>;
+
+    fn produce(&mut self) -> Result<Vec<u8>, Self::In> {
+        let len = self.read_len()?;
+        // Declared lengths are not trusted for allocating up front.
+        let mut frame = Vec::with_capacity(len.min(4096));
+        for _ in 0..len {
+            frame.push(self.producer.produce().map_err(FramesError::Truncated)?);
+        }
+        Ok(frame)
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(FramesError::Inner)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.pending_len = None;
+        self.skip = 0;
+        self.producer.stop(reason).map_err(FramesError::Inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn produce_into_frames() {
+        let bytes = [2, b'a', b'b', 0, 1, b'c'];
+        let mut producer = FramesProducer::new(SliceProducer::new(&bytes), LengthPrefix::U8);
+        let mut buf = [0; 4];
+        assert_eq!(producer.produce_into(&mut buf), Ok(2));
+        assert_eq!(&buf[..2], b"ab");
+        assert_eq!(producer.produce_into(&mut buf), Ok(0));
+        assert_eq!(producer.produce_into(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"c");
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::Inner(SliceEnd))
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let mut buf = [0; 4];
+
+        let bytes = [3, b'a', b'b'];
+        let mut producer = FramesProducer::new(SliceProducer::new(&bytes), LengthPrefix::U8);
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::Truncated(SliceEnd))
+        );
+
+        // A prefix that ends early is truncated as well.
+        let bytes = [0, 3];
+        let prefix = LengthPrefix::U16(Endianness::Big);
+        let mut producer = FramesProducer::new(SliceProducer::new(&bytes[..1]), prefix);
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::Truncated(SliceEnd))
+        );
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        let mut buf = [0; 4];
+        let overlong = [0x80, 0x00];
+        let mut producer = FramesProducer::new(SliceProducer::new(&overlong), LengthPrefix::Varint);
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::InvalidPrefix)
+        );
+
+        let overflowing = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+        let mut producer =
+            FramesProducer::new(SliceProducer::new(&overflowing), LengthPrefix::Varint);
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::InvalidPrefix)
+        );
+    }
+
+    #[test]
+    fn too_long_skips_payload() {
+        let bytes = [3, b'x', b'y', b'z', 1, b'a'];
+        let mut producer =
+            FramesProducer::with_max_len(SliceProducer::new(&bytes), LengthPrefix::U8, 2);
+        let mut buf = [0; 4];
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::TooLong { len: 3 })
+        );
+        assert_eq!(producer.produce_into(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"a");
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::Inner(SliceEnd))
+        );
+    }
+
+    #[test]
+    fn too_long_skip_resumes_after_state_change() {
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::Yield(b'x'),
+            ProduceOutcome::StateChange(()),
+            ProduceOutcome::Yield(b'y'),
+            ProduceOutcome::Yield(b'z'),
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(b'a'),
+        ]);
+        let mut producer = FramesProducer::with_max_len(inner, LengthPrefix::U8, 2);
+        let mut buf = [0; 4];
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::TooLong { len: 3 })
+        );
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(FramesError::Truncated(()))
+        );
+        assert_eq!(producer.produce_into(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"a");
+    }
+
+    #[test]
+    fn buffer_too_small_then_retry() {
+        let bytes = [3, b'a', b'b', b'c'];
+        let mut producer = FramesProducer::new(SliceProducer::new(&bytes), LengthPrefix::U8);
+        let mut small = [0; 2];
+        assert_eq!(
+            producer.produce_into(&mut small),
+            Err(FramesError::BufferTooSmall { len: 3 })
+        );
+        let mut buf = [0; 3];
+        assert_eq!(producer.produce_into(&mut buf), Ok(3));
+        assert_eq!(&buf, b"abc");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_frames_after_too_long() {
+        let bytes = [3, b'x', b'y', b'z', 2, b'a', b'b'];
+        let mut producer =
+            FramesProducer::with_max_len(SliceProducer::new(&bytes), LengthPrefix::U8, 2);
+        assert_eq!(producer.produce(), Err(FramesError::TooLong { len: 3 }));
+        assert_eq!(producer.produce(), Ok(b"ab".to_vec()));
+        assert_eq!(producer.produce(), Err(FramesError::Inner(SliceEnd)));
+    }
+}
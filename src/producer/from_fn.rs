@@ -0,0 +1,41 @@
+use crate::producer::Producer;
+
+/// Produces the results of repeatedly calling a closure, which decides both the items and when
+/// an internal state change happens.
+#[derive(Debug, Clone)]
+pub struct FromFnProducer<F> {
+    f: F,
+}
+
+impl<F> FromFnProducer<F> {
+    pub fn new(f: F) -> Self {
+        FromFnProducer { f }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+}
+
+/// Creates a producer whose `produce` method calls the given closure.
+pub fn from_fn<T, In, F: FnMut() -> Result<T, In>>(f: F) -> FromFnProducer<F> {
+    FromFnProducer::new(f)
+}
+
+impl<T, In, F: FnMut() -> Result<T, In>> Producer for FromFnProducer<F> {
+    type Item = T;
+    type Ex = ();
+    type In = In;
+
+    fn produce(&mut self) -> Result<T, In> {
+        (self.f)()
+    }
+
+    fn slurp(&mut self) -> Result<(), In> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), In> {
+        Ok(())
+    }
+}
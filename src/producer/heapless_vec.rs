@@ -0,0 +1,231 @@
+use core::fmt;
+use core::mem;
+use core::ptr;
+
+use heapless::Vec;
+
+use crate::producer::Producer;
+
+/// Produces the items of a [`heapless::Vec`] by value, front to back, without shifting the
+/// remaining items on every `produce`.
+///
+/// Running out of items is an internal state change (`Err(())`), but unlike most producers this
+/// one stays usable afterwards: once new items have been pushed, `produce` yields them. The space
+/// of already produced items is reclaimed on `slurp`, when the producer runs empty, or when
+/// pushing into a full vector. After `stop` (which drops all remaining items), the producer can be
+/// refilled as well.
+pub struct HeaplessVecProducer<T, const N: usize> {
+    vec: Vec<T, N>,
+    // The items in `..start` have been moved out, the items in `start..vec.len()` are initialized.
+    start: usize,
+}
+
+impl<T, const N: usize> HeaplessVecProducer<T, N> {
+    pub fn new(vec: Vec<T, N>) -> Self {
+        HeaplessVecProducer { vec, start: 0 }
+    }
+
+    /// Returns the items that have not been produced yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            &*ptr::slice_from_raw_parts(
+                self.vec.as_ptr().add(self.start),
+                self.vec.len() - self.start,
+            )
+        }
+    }
+
+    /// Returns how many items have not been produced yet.
+    pub fn len(&self) -> usize {
+        self.vec.len() - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many more items can be pushed, counting the space of produced items that has
+    /// not been reclaimed yet.
+    pub fn free_capacity(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Appends an item to the back, reclaiming the space of produced items if the vector is full.
+    /// Returns the item back if there is no free capacity.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.vec.is_full() {
+            self.compact();
+        }
+        self.vec.push(item)
+    }
+
+    /// Returns the vector of items that have not been produced yet.
+    pub fn into_inner(mut self) -> Vec<T, N> {
+        self.compact();
+        mem::replace(&mut self.vec, Vec::new())
+    }
+
+    // Moves the remaining items to the front of the vector.
+    fn compact(&mut self) {
+        if self.start == 0 {
+            return;
+        }
+
+        let remaining = self.len();
+        unsafe {
+            let base = self.vec.as_mut_ptr();
+            ptr::copy(base.add(self.start), base, remaining);
+            self.vec.set_len(remaining);
+        }
+        self.start = 0;
+    }
+
+    fn drop_remaining(&mut self) {
+        let remaining = self.len();
+        unsafe {
+            let base = self.vec.as_mut_ptr().add(self.start);
+            self.vec.set_len(0);
+            self.start = 0;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base, remaining));
+        }
+    }
+}
+
+impl<T, const N: usize> Producer for HeaplessVecProducer<T, N> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        if self.is_empty() {
+            return Err(());
+        }
+
+        let item = unsafe { ptr::read(self.vec.as_ptr().add(self.start)) };
+        self.start += 1;
+        if self.start == self.vec.len() {
+            unsafe { self.vec.set_len(0) };
+            self.start = 0;
+        }
+        Ok(item)
+    }
+
+    /// Reclaims the space of all produced items.
+    fn slurp(&mut self) -> Result<(), ()> {
+        self.compact();
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.drop_remaining();
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Drop for HeaplessVecProducer<T, N> {
+    fn drop(&mut self) {
+        self.drop_remaining();
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for HeaplessVecProducer<T, N> {
+    fn clone(&self) -> Self {
+        let mut vec = Vec::new();
+        for item in self.as_slice() {
+            // Cannot fail, there are at most `N` remaining items.
+            let _ = vec.push(item.clone());
+        }
+        HeaplessVecProducer::new(vec)
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for HeaplessVecProducer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HeaplessVecProducer")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::testing::DropCounted;
+
+    fn vec_of<const N: usize>(items: &[u8]) -> Vec<u8, N> {
+        Vec::from_slice(items).unwrap()
+    }
+
+    #[test]
+    fn push_into_full_vec_compacts() {
+        let mut producer = HeaplessVecProducer::new(vec_of::<3>(&[1, 2, 3]));
+        assert_eq!(producer.push(4), Err(4));
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.free_capacity(), 1);
+        assert_eq!(producer.push(4), Ok(()));
+        assert_eq!(producer.start, 0);
+        assert_eq!(producer.as_slice(), &[2, 3, 4]);
+        assert_eq!(producer.push(5), Err(5));
+    }
+
+    #[test]
+    fn slurp_compacts() {
+        let mut producer = HeaplessVecProducer::new(vec_of::<4>(&[1, 2, 3]));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.start, 2);
+
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.start, 0);
+        assert_eq!(producer.as_slice(), &[3]);
+        assert_eq!(producer.into_inner(), vec_of::<4>(&[3]));
+    }
+
+    #[test]
+    fn running_empty_reclaims_space() {
+        let mut producer = HeaplessVecProducer::new(vec_of::<2>(&[1, 2]));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.vec.len(), 0);
+
+        assert_eq!(producer.push(3), Ok(()));
+        assert_eq!(producer.push(4), Ok(()));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Ok(4));
+    }
+
+    #[test]
+    fn into_inner_compacts() {
+        let mut producer = HeaplessVecProducer::new(vec_of::<4>(&[1, 2, 3]));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.into_inner(), vec_of::<4>(&[2, 3]));
+    }
+
+    #[test]
+    fn drops_remaining_items_once() {
+        let drops = Cell::new(0);
+        let mut vec: Vec<_, 4> = Vec::new();
+        for i in 0..4 {
+            assert!(vec.push(DropCounted::new(i, &drops)).is_ok());
+        }
+
+        let mut producer = HeaplessVecProducer::new(vec);
+        drop(producer.produce());
+        assert_eq!(drops.get(), 1);
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(drops.get(), 1);
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(drops.get(), 4);
+
+        assert!(producer.push(DropCounted::new(4, &drops)).is_ok());
+        assert!(producer.push(DropCounted::new(5, &drops)).is_ok());
+        drop(producer.produce());
+        drop(producer);
+        assert_eq!(drops.get(), 6);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::producer::Producer;
+
+/// The error returned when setting the position of an [`IndexFnProducer`] beyond its length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PositionOutOfBounds;
+
+/// Produces the results of calling a closure on the indices `0..len`, in order.
+///
+/// The position of the next index can be read and changed at any time. After the last index or
+/// after `stop`, `produce` returns `Err(())` until the position is moved back.
+#[derive(Debug, Clone)]
+pub struct IndexFnProducer<F> {
+    f: F,
+    position: usize,
+    len: usize,
+}
+
+impl<F> IndexFnProducer<F> {
+    pub fn new(len: usize, f: F) -> Self {
+        IndexFnProducer {
+            f,
+            position: 0,
+            len,
+        }
+    }
+
+    /// Returns the index that the next call to `produce` uses.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Sets the index that the next call to `produce` uses. Setting it to `len` is allowed and
+    /// positions the producer at the end.
+    pub fn set_position(&mut self, position: usize) -> Result<(), PositionOutOfBounds> {
+        if position > self.len {
+            return Err(PositionOutOfBounds);
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+}
+
+/// Creates a producer that produces `f(0), f(1), ..., f(len - 1)`.
+pub fn from_index_fn<T, F: FnMut(usize) -> T>(len: usize, f: F) -> IndexFnProducer<F> {
+    IndexFnProducer::new(len, f)
+}
+
+impl<T, F: FnMut(usize) -> T> Producer for IndexFnProducer<F> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        if self.position >= self.len {
+            return Err(());
+        }
+
+        let item = (self.f)(self.position);
+        self.position += 1;
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.position = self.len;
+        Ok(())
+    }
+}
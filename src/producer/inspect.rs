@@ -0,0 +1,68 @@
+use crate::producer::Producer;
+
+/// Calls a function with a reference to every item of the wrapped producer before producing it.
+#[derive(Debug, Clone)]
+pub struct InspectProducer<P, F> {
+    producer: P,
+    f: F,
+}
+
+impl<P, F> InspectProducer<P, F> {
+    pub fn new(producer: P, f: F) -> Self {
+        InspectProducer { producer, f }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer, F: FnMut(&P::Item)> Producer for InspectProducer<P, F> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        let item = self.producer.produce()?;
+        (self.f)(&item);
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn called_once_per_produced_item() {
+        let mut seen = 0;
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::StateChange(()),
+        ])
+        .with_slurp([Ok(())]);
+        let mut producer = InspectProducer::new(inner, |_: &u8| seen += 1);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.stop(()), Ok(()));
+        drop(producer);
+
+        assert_eq!(seen, 3);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::producer::Producer;
+use crate::Either;
+
+/// Alternately produces items of two producers, starting with the first one.
+///
+/// Whether an internal state change of either producer signals the end of its sequence or an
+/// error is decided by a classifying function. When one producer ends, the items of the other one
+/// are produced alone, and only once both have ended is the end of the later one passed on. An
+/// error is passed on immediately.
+///
+/// Calling `produce` again after the end of the later producer has been passed on calls `produce`
+/// on that producer again, the classifying function is not consulted for its results anymore.
+#[derive(Debug, Clone)]
+pub struct InterleaveProducer<A, B, F> {
+    a: A,
+    b: B,
+    is_end: F,
+    on_a: bool,
+    a_done: bool,
+    b_done: bool,
+}
+
+impl<A, B, F> InterleaveProducer<A, B, F> {
+    pub fn new(a: A, b: B, is_end: F) -> Self {
+        InterleaveProducer {
+            a,
+            b,
+            is_end,
+            on_a: true,
+            a_done: false,
+            b_done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+/// Creates a producer that alternates between the items of two producers, treating every internal
+/// state change as the end of a sequence.
+#[allow(clippy::type_complexity)]
+pub fn interleave<A, B>(a: A, b: B) -> InterleaveProducer<A, B, fn(&Either<A::In, B::In>) -> bool>
+where
+    A: Producer,
+    B: Producer<Item = A::Item>,
+{
+    InterleaveProducer::new(a, b, |_| true)
+}
+
+/// Creates a producer that alternates between the items of two producers, using `is_end` to decide
+/// whether an internal state change is the end of a sequence (`true`) or an error (`false`).
+pub fn interleave_by<A, B, F>(a: A, b: B, is_end: F) -> InterleaveProducer<A, B, F>
+where
+    A: Producer,
+    B: Producer<Item = A::Item>,
+    F: FnMut(&Either<A::In, B::In>) -> bool,
+{
+    InterleaveProducer::new(a, b, is_end)
+}
+
+impl<A, B, F> Producer for InterleaveProducer<A, B, F>
+where
+    A: Producer,
+    B: Producer<Item = A::Item>,
+    F: FnMut(&Either<A::In, B::In>) -> bool,
+{
+    type Item = A::Item;
+    type Ex = (A::Ex, B::Ex);
+    type In = Either<A::In, B::In>;
+
+    fn produce(&mut self) -> Result<A::Item, Self::In> {
+        if (self.on_a && !self.a_done) || self.b_done {
+            match self.a.produce() {
+                Ok(item) => {
+                    self.on_a = false;
+                    return Ok(item);
+                }
+                Err(e) => {
+                    let e = Either::Left(e);
+                    if self.b_done || !(self.is_end)(&e) {
+                        return Err(e);
+                    }
+                    self.a_done = true;
+                }
+            }
+        }
+
+        match self.b.produce() {
+            Ok(item) => {
+                self.on_a = true;
+                Ok(item)
+            }
+            Err(e) => {
+                let e = Either::Right(e);
+                if self.a_done || !(self.is_end)(&e) {
+                    return Err(e);
+                }
+                self.b_done = true;
+                self.a.produce().map_err(Either::Left)
+            }
+        }
+    }
+
+    /// Slurps both producers, skipping those whose sequence has ended.
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        if !self.a_done {
+            self.a.slurp().map_err(Either::Left)?;
+        }
+        if !self.b_done {
+            self.b.slurp().map_err(Either::Right)?;
+        }
+        Ok(())
+    }
+
+    /// Stops both producers, even if stopping the first one fails.
+    fn stop(&mut self, (reason_a, reason_b): Self::Ex) -> Result<(), Self::In> {
+        let a = self.a.stop(reason_a).map_err(Either::Left);
+        let b = self.b.stop(reason_b).map_err(Either::Right);
+        a.and(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    use ProduceOutcome::{PanicIfCalled, StateChange, Yield};
+
+    fn scripted<const N: usize>(
+        script: [ProduceOutcome<u8, &str>; N],
+    ) -> ScriptedProducer<u8, &str, ()> {
+        ScriptedProducer::new(script)
+    }
+
+    #[test]
+    fn alternates() {
+        let a = scripted([Yield(1), Yield(3), StateChange("a")]);
+        let b = scripted([Yield(2), Yield(4), StateChange("b")]);
+        let mut producer = interleave(a, b);
+
+        for i in 1..=4 {
+            assert_eq!(producer.produce(), Ok(i));
+        }
+        assert_eq!(producer.produce(), Err(Either::Right("b")));
+    }
+
+    #[test]
+    fn first_ends_first() {
+        let a = scripted([Yield(1), StateChange("a")]);
+        let b = scripted([Yield(2), Yield(4), Yield(6), StateChange("b")]);
+        let mut producer = interleave(a, b);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Ok(4));
+        assert_eq!(producer.produce(), Ok(6));
+        assert_eq!(producer.produce(), Err(Either::Right("b")));
+    }
+
+    #[test]
+    fn second_ends_first() {
+        let a = scripted([Yield(1), Yield(3), Yield(5), StateChange("a"), Yield(7)]);
+        let b = scripted([Yield(2), StateChange("b"), PanicIfCalled]);
+        let mut producer = interleave(a, b);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Ok(5));
+        assert_eq!(producer.produce(), Err(Either::Left("a")));
+        // The producer that ended last is called again.
+        assert_eq!(producer.produce(), Ok(7));
+    }
+
+    #[test]
+    fn errors_are_passed_on_immediately() {
+        let is_end = |e: &Either<&str, &str>| match e {
+            Either::Left(e) | Either::Right(e) => *e == "end",
+        };
+
+        let a = scripted([Yield(1), StateChange("error")]);
+        let b = scripted([Yield(2), PanicIfCalled]);
+        let mut producer = interleave_by(a, b, is_end);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err(Either::Left("error")));
+
+        let a = scripted([Yield(1), PanicIfCalled]);
+        let b = scripted([StateChange("error")]);
+        let mut producer = interleave_by(a, b, is_end);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Err(Either::Right("error")));
+
+        // After the first producer has ended, an error of the second one is still an error.
+        let a = scripted([StateChange("end"), PanicIfCalled]);
+        let b = scripted([Yield(2), StateChange("error")]);
+        let mut producer = interleave_by(a, b, is_end);
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err(Either::Right("error")));
+    }
+}
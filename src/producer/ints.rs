@@ -0,0 +1,161 @@
+use core::fmt::Debug;
+
+use crate::producer::Producer;
+
+/// The order of the bytes of an encoded integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// A primitive integer that can be decoded from a fixed number of bytes.
+pub trait DecodeInt: Sized {
+    /// How many bytes an encoding of the integer consists of.
+    const SIZE: usize;
+
+    /// The encoding of the integer, an array of `SIZE` bytes.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy + Debug;
+
+    /// Decodes the integer from its encoding.
+    fn decode(bytes: Self::Bytes, endianness: Endianness) -> Self;
+}
+
+macro_rules! impl_decode_int {
+    ($($int:ty),*) => {
+        $(
+            impl DecodeInt for $int {
+                const SIZE: usize = core::mem::size_of::<$int>();
+
+                type Bytes = [u8; core::mem::size_of::<$int>()];
+
+                fn decode(bytes: Self::Bytes, endianness: Endianness) -> Self {
+                    match endianness {
+                        Endianness::Little => <$int>::from_le_bytes(bytes),
+                        Endianness::Big => <$int>::from_be_bytes(bytes),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_decode_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// The internal state change of a [`DecodeIntsProducer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecodeIntsError<I> {
+    /// How many bytes of the next integer had been produced by the wrapped producer. Zero means
+    /// the internal state change happened between two integers.
+    pub consumed: usize,
+    /// The internal state change of the wrapped producer.
+    pub inner: I,
+}
+
+/// Decodes fixed-width integers from the bytes of the wrapped producer.
+///
+/// If the wrapped producer performs an internal state change in the middle of an integer, the
+/// bytes that have been read are kept, so decoding can resume if the wrapped producer produces
+/// more bytes.
+#[derive(Debug, Clone)]
+pub struct DecodeIntsProducer<P, T: DecodeInt> {
+    producer: P,
+    endianness: Endianness,
+    buf: T::Bytes,
+    len: usize,
+}
+
+impl<P, T: DecodeInt> DecodeIntsProducer<P, T> {
+    pub fn new(producer: P, endianness: Endianness) -> Self {
+        DecodeIntsProducer {
+            producer,
+            endianness,
+            buf: T::Bytes::default(),
+            len: 0,
+        }
+    }
+
+    pub fn little_endian(producer: P) -> Self {
+        DecodeIntsProducer::new(producer, Endianness::Little)
+    }
+
+    pub fn big_endian(producer: P) -> Self {
+        DecodeIntsProducer::new(producer, Endianness::Big)
+    }
+
+    /// Returns the wrapped producer. Bytes of an incomplete integer are lost.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer<Item = u8>, T: DecodeInt> Producer for DecodeIntsProducer<P, T> {
+    type Item = T;
+    type Ex = P::Ex;
+    type In = DecodeIntsError<P::In>;
+
+    fn produce(&mut self) -> Result<T, Self::In> {
+        while self.len < T::SIZE {
+            match self.producer.produce() {
+                Ok(byte) => {
+                    self.buf.as_mut()[self.len] = byte;
+                    self.len += 1;
+                }
+                Err(inner) => {
+                    return Err(DecodeIntsError {
+                        consumed: self.len,
+                        inner,
+                    })
+                }
+            }
+        }
+
+        self.len = 0;
+        Ok(T::decode(self.buf, self.endianness))
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(|inner| DecodeIntsError {
+            consumed: self.len,
+            inner,
+        })
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.len = 0;
+        self.producer
+            .stop(reason)
+            .map_err(|inner| DecodeIntsError { consumed: 0, inner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+
+    #[test]
+    fn decode() {
+        assert_eq!(u16::decode([0x01, 0x02], Endianness::Little), 0x0201);
+        assert_eq!(u16::decode([0x01, 0x02], Endianness::Big), 0x0102);
+        assert_eq!(i32::decode([0xff; 4], Endianness::Big), -1);
+        assert_eq!(u8::decode([7], Endianness::Little), 7);
+    }
+
+    #[test]
+    fn resumes_after_partial_integer() {
+        let mut producer: DecodeIntsProducer<_, u32> =
+            DecodeIntsProducer::big_endian(SliceProducer::new(&[0x01, 0x02]));
+        assert_eq!(
+            producer.produce(),
+            Err(DecodeIntsError {
+                consumed: 2,
+                inner: SliceEnd,
+            })
+        );
+
+        producer.producer = SliceProducer::new(&[0x03, 0x04]);
+        assert_eq!(producer.produce(), Ok(0x01020304));
+    }
+}
@@ -0,0 +1,237 @@
+use std::boxed::Box;
+use std::io::{self, ErrorKind, Read};
+use std::vec;
+
+use crate::producer::Producer;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// The internal state change of an [`IoReadProducer`].
+#[derive(Debug)]
+pub enum IoReadError {
+    /// The reader has no more bytes.
+    Eof,
+    /// The reader returned an error.
+    Io(io::Error),
+}
+
+/// Produces the bytes of a [`std::io::Read`], reading them into an internal buffer in chunks.
+///
+/// A read of zero bytes is interpreted as the end of the reader, reads that are interrupted are
+/// retried. After `stop` (which drops the reader), `produce` keeps returning `Err(Eof)`.
+#[derive(Debug)]
+pub struct IoReadProducer<R> {
+    reader: Option<R>,
+    buf: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    eof: bool,
+}
+
+impl<R: Read> IoReadProducer<R> {
+    pub fn new(reader: R) -> Self {
+        IoReadProducer::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// Creates a producer whose buffer holds `capacity` bytes.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        assert!(capacity > 0, "IoReadProducer requires a nonzero capacity");
+        IoReadProducer {
+            reader: Some(reader),
+            buf: vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the reader, or `None` if `stop` has been called. Buffered bytes are lost.
+    pub fn into_inner(self) -> Option<R> {
+        self.reader
+    }
+
+    // Performs a single read into the free part of the buffer, returning how many bytes were read.
+    fn fill(&mut self) -> Result<usize, IoReadError> {
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return Err(IoReadError::Eof),
+        };
+
+        loop {
+            match reader.read(&mut self.buf[self.filled..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    return Ok(0);
+                }
+                Ok(read) => {
+                    self.filled += read;
+                    return Ok(read);
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(IoReadError::Io(e)),
+            }
+        }
+    }
+}
+
+impl<R: Read> Producer for IoReadProducer<R> {
+    type Item = u8;
+    type Ex = ();
+    type In = IoReadError;
+
+    fn produce(&mut self) -> Result<u8, IoReadError> {
+        if self.pos == self.filled {
+            if self.eof {
+                return Err(IoReadError::Eof);
+            }
+
+            self.pos = 0;
+            self.filled = 0;
+            if self.fill()? == 0 {
+                return Err(IoReadError::Eof);
+            }
+        }
+
+        let byte = self.buf[self.pos];
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    /// Performs a single read into the buffer, unless the buffer is full or the end of the reader
+    /// has been reached.
+    fn slurp(&mut self) -> Result<(), IoReadError> {
+        if self.eof {
+            return Ok(());
+        }
+
+        self.buf.copy_within(self.pos..self.filled, 0);
+        self.filled -= self.pos;
+        self.pos = 0;
+        if self.filled < self.buf.len() {
+            self.fill()?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), IoReadError> {
+        self.reader = None;
+        self.pos = 0;
+        self.filled = 0;
+        self.eof = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+    use std::io::Cursor;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    // A reader that replays a script of read results, each of which is read in a single call.
+    struct ScriptedReader(VecDeque<io::Result<&'static [u8]>>);
+
+    impl Read for ScriptedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.pop_front() {
+                Some(Ok(bytes)) => {
+                    buf[..bytes.len()].copy_from_slice(bytes);
+                    Ok(bytes.len())
+                }
+                Some(Err(e)) => Err(e),
+                None => Ok(0),
+            }
+        }
+    }
+
+    fn read_all<R: Read>(producer: &mut IoReadProducer<R>) -> (Vec<u8>, IoReadError) {
+        let mut bytes = Vec::new();
+        loop {
+            match producer.produce() {
+                Ok(byte) => bytes.push(byte),
+                Err(e) => return (bytes, e),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_across_buffer_boundaries() {
+        let input: Vec<u8> = (0..10).collect();
+        for capacity in 1..12 {
+            let mut producer = IoReadProducer::with_capacity(capacity, Cursor::new(&input));
+            let (bytes, end) = read_all(&mut producer);
+            assert_eq!(bytes, input);
+            assert!(matches!(end, IoReadError::Eof));
+            assert!(matches!(producer.produce(), Err(IoReadError::Eof)));
+        }
+    }
+
+    #[test]
+    fn empty_reader() {
+        let mut producer = IoReadProducer::new(Cursor::new(Vec::new()));
+        assert!(matches!(producer.produce(), Err(IoReadError::Eof)));
+        assert!(matches!(producer.slurp(), Ok(())));
+        assert!(matches!(producer.produce(), Err(IoReadError::Eof)));
+    }
+
+    #[test]
+    fn retries_interrupted_reads() {
+        let reader = ScriptedReader(VecDeque::from([
+            Err(io::Error::from(ErrorKind::Interrupted)),
+            Ok(&b"ab"[..]),
+            Err(io::Error::from(ErrorKind::Interrupted)),
+            Err(io::Error::from(ErrorKind::Interrupted)),
+            Ok(&b"c"[..]),
+        ]));
+        let mut producer = IoReadProducer::new(reader);
+        let (bytes, end) = read_all(&mut producer);
+        assert_eq!(bytes, b"abc");
+        assert!(matches!(end, IoReadError::Eof));
+    }
+
+    #[test]
+    fn surfaces_errors() {
+        let reader = ScriptedReader(VecDeque::from([
+            Ok(&b"a"[..]),
+            Err(io::Error::other("broken")),
+            Ok(&b"b"[..]),
+        ]));
+        let mut producer = IoReadProducer::new(reader);
+        assert_eq!(producer.produce().ok(), Some(b'a'));
+        match producer.produce() {
+            Err(IoReadError::Io(e)) => {
+                assert_eq!(e.kind(), ErrorKind::Other);
+                assert_eq!(e.to_string(), "broken");
+            }
+            other => panic!("expected an io error, got {:?}", other),
+        }
+        // The reader is used again after an error.
+        assert_eq!(producer.produce().ok(), Some(b'b'));
+    }
+
+    #[test]
+    fn slurp_compacts_and_reads() {
+        let reader = ScriptedReader(VecDeque::from([Ok(&b"abc"[..]), Ok(&b"d"[..])]));
+        let mut producer = IoReadProducer::with_capacity(4, reader);
+        assert_eq!(producer.produce().ok(), Some(b'a'));
+        assert!(producer.slurp().is_ok());
+        assert_eq!(&producer.buf[..producer.filled], b"bcd");
+        let (bytes, end) = read_all(&mut producer);
+        assert_eq!(bytes, b"bcd");
+        assert!(matches!(end, IoReadError::Eof));
+    }
+
+    #[test]
+    fn stop_drops_reader() {
+        let mut producer = IoReadProducer::new(Cursor::new(b"abc"));
+        assert_eq!(producer.produce().ok(), Some(b'a'));
+        assert!(producer.stop(()).is_ok());
+        assert!(matches!(producer.produce(), Err(IoReadError::Eof)));
+        assert!(producer.into_inner().is_none());
+    }
+}
@@ -0,0 +1,173 @@
+use core::iter::Peekable;
+
+use crate::producer::Producer;
+
+/// Produces the items of an iterator, signalling an internal state change once the iterator is
+/// exhausted.
+///
+/// `stop` drops the iterator, after that `produce` keeps returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct IterProducer<I> {
+    iter: Option<I>,
+}
+
+impl<I> IterProducer<I> {
+    pub fn new(iter: I) -> Self {
+        IterProducer { iter: Some(iter) }
+    }
+
+    /// Returns the wrapped iterator, or `None` if `stop` has been called.
+    pub fn into_inner(self) -> Option<I> {
+        self.iter
+    }
+}
+
+/// Creates a producer that produces the items of an iterator.
+pub fn from_iter<I: IntoIterator>(iter: I) -> IterProducer<I::IntoIter> {
+    IterProducer::new(iter.into_iter())
+}
+
+/// Conversion of any iterator into an [`IterProducer`].
+pub trait IntoProducer: Iterator + Sized {
+    fn into_producer(self) -> IterProducer<Self> {
+        IterProducer::new(self)
+    }
+}
+
+impl<I: Iterator> IntoProducer for I {}
+
+impl<I: Iterator> Producer for IterProducer<I> {
+    type Item = I::Item;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<I::Item, ()> {
+        self.iter.as_mut().and_then(Iterator::next).ok_or(())
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.iter = None;
+        Ok(())
+    }
+}
+
+/// Looking at the next item of a [`Peekable`] through the producer interface, using the
+/// lookahead the `Peekable` already maintains.
+///
+/// This is implemented for an [`IterProducer`] that wraps a `Peekable`, created for example with
+/// `iter.peekable().into_producer()`. `Peekable` does not implement [`Producer`] itself, since the
+/// methods of [`ProducerExt`](crate::ProducerExt) would be ambiguous with those of `Iterator`.
+pub trait PeekableProducerExt: Producer {
+    /// Returns a reference to the next item without producing it, or performs the internal state
+    /// change that `produce` would perform.
+    fn peek_produce(&mut self) -> Result<&Self::Item, Self::In>;
+}
+
+impl<I: Iterator> PeekableProducerExt for IterProducer<Peekable<I>> {
+    fn peek_produce(&mut self) -> Result<&I::Item, ()> {
+        self.iter.as_mut().and_then(Peekable::peek).ok_or(())
+    }
+}
+
+/// An iterator over the items of a producer, ending at the first internal state change.
+///
+/// If the producer has not performed an internal state change, dropping the iterator stops it
+/// with the default `Ex`.
+#[derive(Debug)]
+pub struct ProducerIter<P: Producer>
+where
+    P::Ex: Default,
+{
+    producer: P,
+    done: bool,
+}
+
+impl<P: Producer> ProducerIter<P>
+where
+    P::Ex: Default,
+{
+    pub fn new(producer: P) -> Self {
+        ProducerIter {
+            producer,
+            done: false,
+        }
+    }
+}
+
+impl<P: Producer> Iterator for ProducerIter<P>
+where
+    P::Ex: Default,
+{
+    type Item = P::Item;
+
+    fn next(&mut self) -> Option<P::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.producer.produce() {
+            Ok(item) => Some(item),
+            Err(_) => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<P: Producer> Drop for ProducerIter<P>
+where
+    P::Ex: Default,
+{
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.producer.stop(P::Ex::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ProducerExt;
+
+    #[test]
+    fn peek_produce_uses_peekable_lookahead() {
+        let mut producer = [1, 2].iter().copied().peekable().into_producer();
+        assert_eq!(producer.peek_produce(), Ok(&1));
+        assert_eq!(producer.peek_produce(), Ok(&1));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.peek_produce(), Ok(&2));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.peek_produce(), Err(()));
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn peek_produce_after_stop() {
+        let mut producer = [1].iter().copied().peekable().into_producer();
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(producer.peek_produce(), Err(()));
+    }
+
+    // Calling adapter methods with `ProducerExt` in scope must not be ambiguous with `Iterator`.
+    #[test]
+    fn adapters_resolve_to_producer_ext() {
+        let mut producer = (0u8..4).peekable().into_producer().map(|x| x * 2).skip(1);
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Ok(4));
+        assert_eq!(producer.produce(), Ok(6));
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn producer_iter_ends_at_state_change() {
+        let iter = ProducerIter::new(from_iter([1, 2, 3]));
+        assert!(iter.eq([1, 2, 3]));
+    }
+}
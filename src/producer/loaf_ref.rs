@@ -0,0 +1,52 @@
+use loaf::Loaf;
+
+use crate::producer::Producer;
+
+/// Produces references to the items of a loaf.
+///
+/// After producing the last reference, `produce` keeps returning `Err(())`.
+#[derive(Debug)]
+pub struct LoafRefProducer<'a, T> {
+    remaining: &'a [T],
+}
+
+impl<'a, T> LoafRefProducer<'a, T> {
+    pub fn new(loaf: &'a Loaf<T>) -> Self {
+        LoafRefProducer {
+            remaining: loaf.as_slice(),
+        }
+    }
+
+    /// Returns the items that have not been produced yet.
+    pub fn remaining_slice(&self) -> &'a [T] {
+        self.remaining
+    }
+}
+
+impl<'a, T> Clone for LoafRefProducer<'a, T> {
+    fn clone(&self) -> Self {
+        LoafRefProducer {
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, T> Producer for LoafRefProducer<'a, T> {
+    type Item = &'a T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<&'a T, ()> {
+        let (first, rest) = self.remaining.split_first().ok_or(())?;
+        self.remaining = rest;
+        Ok(first)
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        Ok(())
+    }
+}
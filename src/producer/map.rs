@@ -0,0 +1,76 @@
+use crate::producer::Producer;
+
+/// Applies a function to every item of the wrapped producer.
+#[derive(Debug, Clone)]
+pub struct MapProducer<P, F> {
+    producer: P,
+    f: F,
+}
+
+impl<P, F> MapProducer<P, F> {
+    pub fn new(producer: P, f: F) -> Self {
+        MapProducer { producer, f }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<B, P: Producer, F: FnMut(P::Item) -> B> Producer for MapProducer<P, F> {
+    type Item = B;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<B, P::In> {
+        self.producer.produce().map(&mut self.f)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    #[test]
+    fn maps_items_and_forwards_state_changes() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::StateChange("in"),
+            ProduceOutcome::Yield(2),
+        ]);
+        let mut calls = 0;
+        let mut producer = MapProducer::new(inner, |x: u8| {
+            calls += 1;
+            x * 10
+        });
+
+        assert_eq!(producer.produce(), Ok(10));
+        assert_eq!(producer.produce(), Err("in"));
+        assert_eq!(producer.produce(), Ok(20));
+        drop(producer);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn forwards_slurp_and_stop() {
+        let inner = ScriptedProducer::<u8, (), &str>::new([]).with_slurp([Err(())]);
+        let mut producer = MapProducer::new(inner, |x: u8| x);
+
+        assert_eq!(producer.slurp(), Err(()));
+        assert_eq!(producer.stop("done"), Ok(()));
+        assert_eq!(
+            producer.into_inner().log(),
+            &[ProducerCall::Slurp, ProducerCall::Stop("done")]
+        );
+    }
+}
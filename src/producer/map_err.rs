@@ -0,0 +1,68 @@
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+
+use loaf::Loaf;
+
+use crate::producer::{Producer, ProducerFrom, ProducerTo};
+
+/// Applies a function to every internal state change of the wrapped producer.
+#[derive(Debug, Clone)]
+pub struct MapErrProducer<P, F> {
+    producer: P,
+    f: F,
+}
+
+impl<P, F> MapErrProducer<P, F> {
+    pub fn new(producer: P, f: F) -> Self {
+        MapErrProducer { producer, f }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<E, P: Producer, F: FnMut(P::In) -> E> Producer for MapErrProducer<P, F> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = E;
+
+    fn produce(&mut self) -> Result<P::Item, E> {
+        self.producer.produce().map_err(&mut self.f)
+    }
+
+    fn produce_many(&mut self, buf: &mut [MaybeUninit<P::Item>]) -> Result<NonZeroUsize, E> {
+        self.producer.produce_many(buf).map_err(&mut self.f)
+    }
+
+    fn slurp(&mut self) -> Result<(), E> {
+        self.producer.slurp().map_err(&mut self.f)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), E> {
+        self.producer.stop(reason).map_err(&mut self.f)
+    }
+}
+
+impl<E, P: ProducerFrom, F: FnMut(P::In) -> E> ProducerFrom for MapErrProducer<P, F> {
+    fn produce_from(&mut self) -> Result<*const P::Item, E> {
+        self.producer.produce_from().map_err(&mut self.f)
+    }
+}
+
+impl<E, P: ProducerTo, F: FnMut(P::In) -> E> ProducerTo for MapErrProducer<P, F> {
+    unsafe fn produce_to(
+        &mut self,
+        to: *mut Loaf<MaybeUninit<P::Item>>,
+    ) -> Result<NonZeroUsize, E> {
+        self.producer.produce_to(to).map_err(&mut self.f)
+    }
+
+    unsafe fn produce_to_1(&mut self, to: *mut MaybeUninit<P::Item>) -> Result<(), E> {
+        self.producer.produce_to_1(to).map_err(&mut self.f)
+    }
+
+    unsafe fn slurp_produce_to_1(&mut self, to: *mut MaybeUninit<P::Item>) -> Result<(), E> {
+        self.producer.slurp_produce_to_1(to).map_err(&mut self.f)
+    }
+}
@@ -0,0 +1,387 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+mod array;
+mod bits;
+mod buffered;
+mod bytes;
+mod chain;
+mod checksum;
+mod chunks;
+mod cycle;
+mod dedup;
+mod either;
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
+mod empty;
+mod enumerate;
+mod ext;
+mod fallback;
+mod filter;
+mod flat_map;
+mod flatten;
+mod frames;
+mod from_fn;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
+mod index_fn;
+mod inspect;
+mod interleave;
+mod ints;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+mod loaf_ref;
+mod map;
+mod map_err;
+#[cfg(feature = "std")]
+mod mpsc;
+mod once;
+mod option;
+mod peekable;
+mod prng;
+mod range;
+mod repeat;
+mod result;
+mod retry;
+mod ring_slice;
+mod rle;
+mod scan;
+mod skip;
+mod slice;
+mod split;
+mod str;
+mod successors;
+mod take;
+mod unfold;
+mod utf8;
+mod varint;
+#[cfg(feature = "alloc")]
+mod vec;
+#[cfg(feature = "alloc")]
+mod vec_deque;
+mod windows;
+mod zip;
+
+pub use array::ArrayProducer;
+pub use bits::BitsProducer;
+pub use buffered::BufferedProducer;
+pub use bytes::ByteProducer;
+pub use chain::ChainProducer;
+pub use checksum::{ChecksumVerifyError, ChecksumVerifyingProducer};
+pub use chunks::ChunksProducer;
+pub use cycle::CycleProducer;
+pub use dedup::DedupProducer;
+pub use either::EitherProducer;
+#[cfg(feature = "embedded-io")]
+pub use embedded_io::{EmbeddedIoReadError, EmbeddedIoReadProducer};
+pub use empty::{empty, EmptyProducer};
+pub use enumerate::EnumerateProducer;
+pub use ext::ProducerExt;
+pub use fallback::FallbackProducer;
+pub use filter::FilterProducer;
+pub use flat_map::FlatMapProducer;
+pub use flatten::FlattenProducer;
+pub(crate) use frames::MAX_PREFIX_LEN;
+pub use frames::{FramesError, FramesProducer, LengthPrefix};
+pub use from_fn::{from_fn, FromFnProducer};
+#[cfg(feature = "heapless")]
+pub use heapless_vec::HeaplessVecProducer;
+pub use index_fn::{from_index_fn, IndexFnProducer, PositionOutOfBounds};
+pub use inspect::InspectProducer;
+pub use interleave::{interleave, interleave_by, InterleaveProducer};
+pub use ints::{DecodeInt, DecodeIntsError, DecodeIntsProducer, Endianness};
+#[cfg(feature = "std")]
+pub use io::{IoReadError, IoReadProducer};
+pub use iter::{from_iter, IntoProducer, IterProducer, PeekableProducerExt, ProducerIter};
+pub use loaf_ref::LoafRefProducer;
+pub use map::MapProducer;
+pub use map_err::MapErrProducer;
+#[cfg(feature = "std")]
+pub use mpsc::ReceiverProducer;
+pub use once::{once, once_with, OnceProducer, OnceWithProducer};
+pub use peekable::PeekableProducer;
+pub use prng::{prng, prng_u32, prng_u8, PrngItem, PrngProducer};
+pub use range::{range, RangeProducer};
+pub use repeat::{
+    repeat, repeat_n, repeat_with, RepeatNProducer, RepeatProducer, RepeatWithProducer,
+};
+pub use result::ResultProducer;
+pub use retry::RetryProducer;
+pub use ring_slice::{RingSliceError, RingSliceProducer};
+pub use rle::{RleDecodeError, RleDecodeProducer};
+pub use scan::ScanProducer;
+pub use skip::SkipProducer;
+pub use slice::{SliceEnd, SliceProducer};
+pub use split::{split, SplitIn, SplitIntoError, SplitProducer};
+#[cfg(feature = "alloc")]
+pub use split::{split_vec, SplitVecProducer};
+pub use str::{CharIndicesProducer, CharProducer, LinesProducer};
+pub use successors::{successors, SuccessorsProducer};
+pub use take::TakeProducer;
+pub use unfold::{unfold, UnfoldProducer};
+pub use utf8::{DecodeUtf8Error, DecodeUtf8Producer};
+pub use varint::{VarintError, VarintProducer, ZigzagVarintProducer};
+#[cfg(feature = "alloc")]
+pub use vec::VecProducer;
+pub use windows::WindowsProducer;
+pub use zip::ZipProducer;
+
+/// A sequence manipulator that moves items out of a sequence, front to back.
+///
+/// `produce` corresponds to a `Read` followed by a `Next`, `slurp` corresponds to `SlurpNext`, and
+/// `stop` corresponds to `StopRead`. As with all sequence manipulators, a method that returns an
+/// `Err` (an *internal state change*) must not have modified the sequence, and calling any method
+/// after an internal state change or after `stop` results in unspecified behavior unless an
+/// implementation documents otherwise.
+pub trait Producer {
+    /// The type of the items in the sequence.
+    type Item;
+    /// Information about why `stop` is being called.
+    type Ex;
+    /// Information about an internal state change.
+    type In;
+
+    /// Moves the item at the current position out of the sequence and advances the position.
+    fn produce(&mut self) -> Result<Self::Item, Self::In>;
+
+    /// Moves items into the start of `buf` until it is full or an internal state change happens,
+    /// and returns how many items were moved.
+    ///
+    /// Only performs an internal state change if not a single item could be produced. An internal
+    /// state change that happens after producing at least one item is discarded, calling a method
+    /// afterwards then follows the same rules as calling a method after that internal state change.
+    ///
+    /// Panics if `buf` is empty.
+    fn produce_many(
+        &mut self,
+        buf: &mut [MaybeUninit<Self::Item>],
+    ) -> Result<NonZeroUsize, Self::In> {
+        assert!(!buf.is_empty(), "called produce_many with an empty buffer");
+        buf[0] = MaybeUninit::new(self.produce()?);
+
+        let mut count = 1;
+        while count < buf.len() {
+            match self.produce() {
+                Ok(item) => {
+                    buf[count] = MaybeUninit::new(item);
+                    count += 1;
+                }
+                Err(_) => break,
+            }
+        }
+        Ok(unsafe { NonZeroUsize::new_unchecked(count) })
+    }
+
+    /// Moves items into an internal buffer, even if that buffer is not empty.
+    fn slurp(&mut self) -> Result<(), Self::In>;
+
+    /// Notifies the producer that `produce` will not be called anymore.
+    fn stop(&mut self, reason: Self::Ex) -> Result<(), Self::In>;
+}
+
+/// A producer that knows bounds on how many items it will produce before its next internal state
+/// change.
+pub trait SizedProducer: Producer {
+    /// Returns a lower bound and an optional upper bound on the number of remaining items, with
+    /// the same meaning as [`Iterator::size_hint`].
+    fn size_hint(&self) -> (usize, Option<usize>);
+}
+
+/// Produces items until the producer performs an internal state change, collecting them into a
+/// vector whose capacity is preallocated according to the size hint. The internal state change is
+/// discarded.
+#[cfg(feature = "alloc")]
+pub fn produce_all_into_vec<P: SizedProducer>(p: &mut P) -> Vec<P::Item> {
+    let mut items = Vec::with_capacity(p.size_hint().0);
+    while let Ok(item) = p.produce() {
+        items.push(item);
+    }
+    items
+}
+
+/// A producer that can lend out items rather than moving them out of the sequence.
+pub trait ProducerFrom: Producer {
+    /// Advances the position and returns a pointer to the item that was at the old position.
+    ///
+    /// The pointer must be treated like a shared reference that is valid until the next method
+    /// call on `self`.
+    fn produce_from(&mut self) -> Result<*const Self::Item, Self::In>;
+}
+
+/// A producer that can move items directly into memory provided by the caller.
+pub trait ProducerTo: Producer {
+    /// Moves a nonzero number of items into the start of the loaf, returning how many.
+    ///
+    /// # Safety
+    ///
+    /// `to` must be valid for writes. When returning `Ok(n)`, exactly the first `n` slots have
+    /// been initialized, when returning `Err` no slot has been written to.
+    unsafe fn produce_to(
+        &mut self,
+        to: *mut Loaf<MaybeUninit<Self::Item>>,
+    ) -> Result<NonZeroUsize, Self::In>;
+
+    /// Moves exactly one item into the given slot.
+    ///
+    /// # Safety
+    ///
+    /// `to` must be valid for writes. The slot has been initialized if and only if this returns
+    /// `Ok`.
+    unsafe fn produce_to_1(&mut self, to: *mut MaybeUninit<Self::Item>) -> Result<(), Self::In> {
+        let to = ptr::slice_from_raw_parts_mut(to, 0) as *mut Loaf<MaybeUninit<Self::Item>>;
+        self.produce_to(to).map(|_| ())
+    }
+
+    /// Slurps, then moves exactly one item into the given slot.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`ProducerTo::produce_to_1`].
+    unsafe fn slurp_produce_to_1(
+        &mut self,
+        to: *mut MaybeUninit<Self::Item>,
+    ) -> Result<(), Self::In> {
+        self.slurp()?;
+        self.produce_to_1(to)
+    }
+}
+
+impl<P: Producer + ?Sized> Producer for &mut P {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        (**self).produce()
+    }
+
+    fn produce_many(&mut self, buf: &mut [MaybeUninit<P::Item>]) -> Result<NonZeroUsize, P::In> {
+        (**self).produce_many(buf)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        (**self).slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        (**self).stop(reason)
+    }
+}
+
+impl<P: SizedProducer + ?Sized> SizedProducer for &mut P {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl<P: ProducerFrom + ?Sized> ProducerFrom for &mut P {
+    fn produce_from(&mut self) -> Result<*const P::Item, P::In> {
+        (**self).produce_from()
+    }
+}
+
+impl<P: ProducerTo + ?Sized> ProducerTo for &mut P {
+    unsafe fn produce_to(
+        &mut self,
+        to: *mut Loaf<MaybeUninit<P::Item>>,
+    ) -> Result<NonZeroUsize, P::In> {
+        (**self).produce_to(to)
+    }
+
+    unsafe fn produce_to_1(&mut self, to: *mut MaybeUninit<P::Item>) -> Result<(), P::In> {
+        (**self).produce_to_1(to)
+    }
+
+    unsafe fn slurp_produce_to_1(&mut self, to: *mut MaybeUninit<P::Item>) -> Result<(), P::In> {
+        (**self).slurp_produce_to_1(to)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Producer + ?Sized> Producer for Box<P> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        (**self).produce()
+    }
+
+    fn produce_many(&mut self, buf: &mut [MaybeUninit<P::Item>]) -> Result<NonZeroUsize, P::In> {
+        (**self).produce_many(buf)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        (**self).slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        (**self).stop(reason)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: SizedProducer + ?Sized> SizedProducer for Box<P> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: ProducerFrom + ?Sized> ProducerFrom for Box<P> {
+    fn produce_from(&mut self) -> Result<*const P::Item, P::In> {
+        (**self).produce_from()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: ProducerTo + ?Sized> ProducerTo for Box<P> {
+    unsafe fn produce_to(
+        &mut self,
+        to: *mut Loaf<MaybeUninit<P::Item>>,
+    ) -> Result<NonZeroUsize, P::In> {
+        (**self).produce_to(to)
+    }
+
+    unsafe fn produce_to_1(&mut self, to: *mut MaybeUninit<P::Item>) -> Result<(), P::In> {
+        (**self).produce_to_1(to)
+    }
+
+    unsafe fn slurp_produce_to_1(&mut self, to: *mut MaybeUninit<P::Item>) -> Result<(), P::In> {
+        (**self).slurp_produce_to_1(to)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn boxed_trait_objects_through_adapter() {
+        let mut producers: Vec<Box<dyn Producer<Item = u8, In = SliceEnd, Ex = ()>>> = vec![
+            Box::new(SliceProducer::new(&[1, 2])),
+            Box::new(SliceProducer::new(&[3, 4, 5]).map(|x| x * 10)),
+        ];
+
+        let mut items = Vec::new();
+        for producer in producers.drain(..) {
+            let mut doubled = producer.map(|x| x * 2);
+            while let Ok(item) = doubled.produce() {
+                items.push(item);
+            }
+            assert_eq!(doubled.stop(()), Ok(()));
+        }
+        assert_eq!(items, [2, 4, 60, 80, 100]);
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, RecvError, TryRecvError};
+
+use crate::producer::Producer;
+
+const DEFAULT_CAPACITY: usize = 32;
+
+/// Produces the values sent over a [`std::sync::mpsc`] channel, blocking until a value is
+/// available.
+///
+/// `slurp` moves up to a fixed number of already-sent values into an internal buffer without
+/// blocking. Once all senders have disconnected and the buffer is empty, `produce` returns
+/// `Err(RecvError)`. `stop` drops the receiver (and any buffered values), afterwards `produce`
+/// keeps returning `Err(RecvError)`.
+#[derive(Debug)]
+pub struct ReceiverProducer<T> {
+    receiver: Option<Receiver<T>>,
+    buf: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> ReceiverProducer<T> {
+    pub fn new(receiver: Receiver<T>) -> Self {
+        ReceiverProducer::with_capacity(DEFAULT_CAPACITY, receiver)
+    }
+
+    /// Creates a producer that buffers at most `capacity` values on `slurp`.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize, receiver: Receiver<T>) -> Self {
+        assert!(capacity > 0, "ReceiverProducer requires a nonzero capacity");
+        ReceiverProducer {
+            receiver: Some(receiver),
+            buf: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the receiver, or `None` if `stop` has been called or the channel was found to be
+    /// disconnected. Buffered values are lost.
+    pub fn into_inner(self) -> Option<Receiver<T>> {
+        self.receiver
+    }
+}
+
+impl<T> Producer for ReceiverProducer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = RecvError;
+
+    fn produce(&mut self) -> Result<T, RecvError> {
+        if let Some(item) = self.buf.pop_front() {
+            return Ok(item);
+        }
+
+        let result = match self.receiver.as_ref() {
+            Some(receiver) => receiver.recv(),
+            None => return Err(RecvError),
+        };
+        if result.is_err() {
+            self.receiver = None;
+        }
+        result
+    }
+
+    /// Moves values that have already been sent into the buffer until it is full or no value is
+    /// immediately available. Never blocks.
+    fn slurp(&mut self) -> Result<(), RecvError> {
+        while self.buf.len() < self.capacity {
+            let receiver = match self.receiver.as_ref() {
+                Some(receiver) => receiver,
+                None => break,
+            };
+
+            match receiver.try_recv() {
+                Ok(item) => self.buf.push_back(item),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => self.receiver = None,
+            }
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), RecvError> {
+        self.receiver = None;
+        self.buf.clear();
+        Ok(())
+    }
+}
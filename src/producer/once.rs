@@ -0,0 +1,144 @@
+use core::iter::Once;
+
+use crate::producer::{Producer, SizedProducer};
+
+/// Produces a single item.
+///
+/// After producing the item or after `stop` (which drops the item), `produce` keeps returning
+/// `Err(())`.
+#[derive(Debug, Clone)]
+pub struct OnceProducer<T> {
+    item: Option<T>,
+}
+
+impl<T> OnceProducer<T> {
+    pub fn new(item: T) -> Self {
+        OnceProducer { item: Some(item) }
+    }
+}
+
+/// Converts the iterator into a producer of its item, if it has not been produced yet.
+///
+/// `Once` does not implement [`Producer`] itself, since the methods of
+/// [`ProducerExt`](crate::ProducerExt) would be ambiguous with those of `Iterator`.
+impl<T> From<Once<T>> for OnceProducer<T> {
+    fn from(mut iter: Once<T>) -> Self {
+        OnceProducer { item: iter.next() }
+    }
+}
+
+/// Creates a producer that produces the given item and nothing else.
+pub fn once<T>(item: T) -> OnceProducer<T> {
+    OnceProducer::new(item)
+}
+
+impl<T> Producer for OnceProducer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        self.item.take().ok_or(())
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.item = None;
+        Ok(())
+    }
+}
+
+impl<T> SizedProducer for OnceProducer<T> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.item.is_some() as usize;
+        (len, Some(len))
+    }
+}
+
+/// Produces a single item, computed by a closure on the first call to `produce`.
+///
+/// After producing the item or after `stop` (which drops the closure without calling it),
+/// `produce` keeps returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct OnceWithProducer<F> {
+    f: Option<F>,
+}
+
+impl<F> OnceWithProducer<F> {
+    pub fn new(f: F) -> Self {
+        OnceWithProducer { f: Some(f) }
+    }
+}
+
+/// Creates a producer that produces the result of calling the given closure and nothing else.
+pub fn once_with<T, F: FnOnce() -> T>(f: F) -> OnceWithProducer<F> {
+    OnceWithProducer::new(f)
+}
+
+impl<T, F: FnOnce() -> T> Producer for OnceWithProducer<F> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        self.f.take().map(|f| f()).ok_or(())
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.f = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ProducerExt;
+
+    #[test]
+    fn once_sequence() {
+        let mut producer = once(7);
+        assert_eq!(producer.size_hint(), (1, Some(1)));
+        assert_eq!(producer.produce(), Ok(7));
+        assert_eq!(producer.size_hint(), (0, Some(0)));
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn from_iter_once() {
+        let mut producer = OnceProducer::from(core::iter::once(7)).map(|x| x + 1);
+        assert_eq!(producer.produce(), Ok(8));
+        assert_eq!(producer.produce(), Err(()));
+
+        let mut exhausted = core::iter::once(7);
+        exhausted.next();
+        let mut producer = OnceProducer::from(exhausted);
+        assert_eq!(producer.size_hint(), (0, Some(0)));
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn once_with_calls_closure_lazily() {
+        let mut called = false;
+        let mut producer = once_with(|| {
+            called = true;
+            3
+        });
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err(()));
+        assert!(called);
+
+        let mut producer = once_with(|| -> u8 { panic!("called after stop") });
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(producer.produce(), Err(()));
+    }
+}
@@ -0,0 +1,86 @@
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+
+use loaf::Loaf;
+
+use crate::producer::{Producer, ProducerTo, SizedProducer};
+
+/// Produces the contained item, if any.
+///
+/// Producing from `None` results in `Err(())`. After producing the item or after `stop`, the
+/// option is `None`, so `produce` keeps returning `Err(())`.
+impl<T> Producer for Option<T> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        self.take().ok_or(())
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        *self = None;
+        Ok(())
+    }
+}
+
+impl<T> SizedProducer for Option<T> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.is_some() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> ProducerTo for Option<T> {
+    unsafe fn produce_to(&mut self, to: *mut Loaf<MaybeUninit<T>>) -> Result<NonZeroUsize, ()> {
+        let item = self.produce()?;
+        (*to).first_mut().write(item);
+        Ok(NonZeroUsize::new_unchecked(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::ptr;
+
+    #[test]
+    fn bulk_and_single_paths_agree() {
+        let mut single = Some(5);
+        assert_eq!(single.produce(), Ok(5));
+        assert_eq!(single.produce(), Err(()));
+
+        let mut many = Some(5);
+        let mut buf = [MaybeUninit::uninit(); 3];
+        assert_eq!(many.produce_many(&mut buf).map(NonZeroUsize::get), Ok(1));
+        assert_eq!(unsafe { buf[0].assume_init() }, 5);
+        assert_eq!(many.produce_many(&mut buf), Err(()));
+
+        let mut to = Some(5);
+        let loaf = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr(), 2) as *mut Loaf<MaybeUninit<_>>;
+        assert_eq!(unsafe { to.produce_to(loaf) }.map(NonZeroUsize::get), Ok(1));
+        assert_eq!(unsafe { buf[0].assume_init() }, 5);
+        assert_eq!(unsafe { to.produce_to(loaf) }, Err(()));
+    }
+
+    #[test]
+    fn option_sequences() {
+        let mut some = Some('a');
+        assert_eq!(some.produce(), Ok('a'));
+        assert_eq!(some.produce(), Err(()));
+        assert_eq!(some.produce(), Err(()));
+
+        let mut none: Option<char> = None;
+        assert_eq!(none.size_hint(), (0, Some(0)));
+        assert_eq!(none.produce(), Err(()));
+
+        let mut stopped = Some('a');
+        assert_eq!(stopped.stop(()), Ok(()));
+        assert_eq!(stopped.produce(), Err(()));
+    }
+}
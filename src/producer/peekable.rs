@@ -0,0 +1,121 @@
+use crate::producer::Producer;
+
+/// Allows looking at the next item of the wrapped producer without producing it.
+#[derive(Debug, Clone)]
+pub struct PeekableProducer<P: Producer> {
+    producer: P,
+    peeked: Option<Result<P::Item, P::In>>,
+}
+
+impl<P: Producer> PeekableProducer<P> {
+    pub fn new(producer: P) -> Self {
+        PeekableProducer {
+            producer,
+            peeked: None,
+        }
+    }
+
+    /// Returns a reference to the next item, without advancing the position.
+    ///
+    /// An internal state change is peeked just like an item: the next call to `produce` returns
+    /// it.
+    pub fn peek(&mut self) -> Result<&P::Item, &P::In> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.producer.produce());
+        }
+        self.peeked.as_ref().unwrap().as_ref()
+    }
+}
+
+impl<P: Producer> Producer for PeekableProducer<P> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.producer.produce(),
+        }
+    }
+
+    /// Slurps the wrapped producer, then fills the lookahead if it is empty.
+    ///
+    /// An internal state change while filling the lookahead is returned directly rather than
+    /// peeked.
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()?;
+        if self.peeked.is_none() {
+            self.peeked = Some(Ok(self.producer.produce()?));
+        }
+        Ok(())
+    }
+
+    /// Drops the lookahead and stops the wrapped producer.
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.peeked = None;
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    #[test]
+    fn peek_then_produce_returns_same_item() {
+        let mut producer = PeekableProducer::new(SliceProducer::new(&[1, 2]));
+        assert_eq!(producer.peek(), Ok(&1));
+        assert_eq!(producer.peek(), Ok(&1));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.peek(), Err(&SliceEnd));
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    #[test]
+    fn peeked_state_change_is_replayed() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::StateChange("in"),
+            ProduceOutcome::Yield(2),
+        ]);
+        let mut producer = PeekableProducer::new(inner);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.peek(), Err(&"in"));
+        assert_eq!(producer.peek(), Err(&"in"));
+        assert_eq!(producer.produce(), Err("in"));
+        assert_eq!(producer.produce(), Ok(2));
+    }
+
+    #[test]
+    fn slurp_fills_lookahead() {
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let mut producer = PeekableProducer::new(inner);
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.produce(), Ok(1));
+    }
+
+    #[test]
+    fn stop_drops_lookahead() {
+        let inner = ScriptedProducer::<u8, (), &str>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let mut producer = PeekableProducer::new(inner);
+        assert_eq!(producer.peek(), Ok(&1));
+        assert_eq!(producer.stop("done"), Ok(()));
+        assert_eq!(producer.peeked, None);
+        assert_eq!(
+            producer.producer.log(),
+            &[ProducerCall::Produce, ProducerCall::Stop("done")]
+        );
+    }
+}
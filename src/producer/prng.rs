@@ -0,0 +1,192 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use crate::producer::Producer;
+
+/// An unsigned integer that can be produced by a [`PrngProducer`].
+pub trait PrngItem {
+    /// Creates the integer from 64 pseudo-random bits, using the high bits if it is smaller.
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! impl_prng_item {
+    ($($int:ty),*) => {
+        $(
+            impl PrngItem for $int {
+                fn from_bits(bits: u64) -> Self {
+                    (bits >> (64 - 8 * core::mem::size_of::<$int>())) as $int
+                }
+            }
+        )*
+    };
+}
+
+impl_prng_item!(u8, u16, u32, u64);
+
+/// Produces pseudo-random integers, forever.
+///
+/// Uses the xoshiro256++ generator, with the state initialized from a `u64` seed via splitmix64.
+/// The same seed always yields the same sequence, and cloning the producer forks an identical
+/// stream. This is meant for generating test data, not for anything that needs to be
+/// unpredictable.
+///
+/// `stop` does not do anything, the producer keeps producing afterwards.
+#[derive(Debug, Clone)]
+pub struct PrngProducer<T = u64> {
+    state: [u64; 4],
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> PrngProducer<T> {
+    pub fn new(seed: u64) -> Self {
+        let mut seed = seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            *word = splitmix64(&mut seed);
+        }
+        PrngProducer {
+            state,
+            _item: PhantomData,
+        }
+    }
+
+    /// Returns the next 64 pseudo-random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Returns the next 32 pseudo-random bits.
+    pub fn next_u32(&mut self) -> u32 {
+        u32::from_bits(self.next_u64())
+    }
+
+    /// Returns the next 8 pseudo-random bits.
+    pub fn next_u8(&mut self) -> u8 {
+        u8::from_bits(self.next_u64())
+    }
+}
+
+/// Creates a producer of pseudo-random `u64`s from the given seed.
+pub fn prng(seed: u64) -> PrngProducer<u64> {
+    PrngProducer::new(seed)
+}
+
+/// Creates a producer of pseudo-random `u32`s from the given seed.
+pub fn prng_u32(seed: u64) -> PrngProducer<u32> {
+    PrngProducer::new(seed)
+}
+
+/// Creates a producer of pseudo-random bytes from the given seed.
+pub fn prng_u8(seed: u64) -> PrngProducer<u8> {
+    PrngProducer::new(seed)
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl<T: PrngItem> Producer for PrngProducer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn produce(&mut self) -> Result<T, Infallible> {
+        Ok(T::from_bits(self.next_u64()))
+    }
+
+    fn slurp(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The first outputs of the reference implementation
+    // (https://prng.di.unimi.it/xoshiro256plusplus.c), started from the state `[1, 2, 3, 4]`.
+    #[test]
+    fn matches_reference_output() {
+        let mut producer = PrngProducer::<u64> {
+            state: [1, 2, 3, 4],
+            _item: PhantomData,
+        };
+        let expected = [
+            41943041,
+            58720359,
+            3588806011781223,
+            3591011842654386,
+            9228616714210784205,
+            9973669472204895162,
+            14011001112246962877,
+            12406186145184390807,
+            15849039046786891736,
+            10450023813501588000,
+        ];
+        for value in expected {
+            assert_eq!(producer.produce(), Ok(value));
+        }
+    }
+
+    // The reference implementation, seeded with the outputs of the reference splitmix64
+    // implementation (https://prng.di.unimi.it/splitmix64.c) for the seed zero.
+    #[test]
+    fn matches_reference_seeding() {
+        let mut producer = prng(0);
+        let expected = [
+            0x5317_5d61_490b_23df,
+            0x61da_6f3d_c380_d507,
+            0x5c0f_df91_ec9a_7bfc,
+            0x02ee_bf8c_3bbe_5e1a,
+        ];
+        for value in expected {
+            assert_eq!(producer.produce(), Ok(value));
+        }
+    }
+
+    #[test]
+    fn seeding_is_deterministic() {
+        let mut a = prng(42);
+        let mut b = prng(42);
+        let mut c = prng(43);
+        let mut differs = false;
+        for _ in 0..16 {
+            let value = a.next_u64();
+            assert_eq!(b.next_u64(), value);
+            differs |= c.next_u64() != value;
+        }
+        assert!(differs);
+
+        let mut fork = a.clone();
+        assert_eq!(fork.next_u64(), a.next_u64());
+    }
+
+    #[test]
+    fn narrow_items_use_high_bits() {
+        let mut wide = prng(7);
+        let mut bytes = prng_u8(7);
+        let mut words = prng_u32(7);
+        for _ in 0..8 {
+            let bits = wide.next_u64();
+            assert_eq!(bytes.produce(), Ok((bits >> 56) as u8));
+            assert_eq!(words.produce(), Ok((bits >> 32) as u32));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use core::ops::Range;
+
+use crate::producer::Producer;
+
+/// Produces the integers of a range in ascending order, signalling an internal state change once
+/// the range is empty.
+///
+/// `stop` empties the range, after that `produce` keeps returning `Err(())`.
+///
+/// This wraps the range rather than implementing [`Producer`] for `Range` itself, since `Range` is
+/// an [`Iterator`], and the methods of [`ProducerExt`](crate::ProducerExt) would be ambiguous
+/// with those of `Iterator` otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeProducer<T> {
+    range: Range<T>,
+}
+
+impl<T> RangeProducer<T> {
+    pub fn new(range: Range<T>) -> Self {
+        RangeProducer { range }
+    }
+
+    /// Returns the integers that have not been produced yet.
+    pub fn into_inner(self) -> Range<T> {
+        self.range
+    }
+}
+
+/// Creates a producer of the integers in the given range.
+pub fn range<T>(range: Range<T>) -> RangeProducer<T> {
+    RangeProducer::new(range)
+}
+
+impl<T> From<Range<T>> for RangeProducer<T> {
+    fn from(range: Range<T>) -> Self {
+        RangeProducer::new(range)
+    }
+}
+
+macro_rules! range_producer {
+    ($($int:ty)*) => {$(
+        impl Producer for RangeProducer<$int> {
+            type Item = $int;
+            type Ex = ();
+            type In = ();
+
+            fn produce(&mut self) -> Result<$int, ()> {
+                self.range.next().ok_or(())
+            }
+
+            fn slurp(&mut self) -> Result<(), ()> {
+                Ok(())
+            }
+
+            fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+                self.range.start = self.range.end;
+                Ok(())
+            }
+        }
+    )*};
+}
+
+range_producer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ProducerExt;
+
+    #[test]
+    fn produces_range() {
+        let mut producer = range(-2i8..1);
+        assert_eq!(producer.produce(), Ok(-2));
+        assert_eq!(producer.produce(), Ok(-1));
+        assert_eq!(producer.produce(), Ok(0));
+        assert_eq!(producer.produce(), Err(()));
+
+        let mut empty = range(5usize..5);
+        assert_eq!(empty.produce(), Err(()));
+    }
+
+    #[test]
+    fn stop_empties_range() {
+        let mut producer = range(0u32..10);
+        assert_eq!(producer.produce(), Ok(0));
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(producer.produce(), Err(()));
+        assert!(producer.into_inner().is_empty());
+    }
+
+    // Calling adapter methods with `ProducerExt` in scope must not be ambiguous with `Iterator`.
+    #[test]
+    fn adapters_resolve_to_producer_ext() {
+        let mut producer = range(0u8..3).map(|x| x + 1).filter(|x| *x != 2);
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err(()));
+
+        let mut producer = RangeProducer::from(0u8..5).skip(1).take(2).enumerate();
+        assert_eq!(producer.produce(), Ok((0, 1)));
+        assert_eq!(producer.produce(), Ok((1, 2)));
+        assert!(producer.produce().is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use core::convert::Infallible;
+
+use crate::producer::{Producer, SizedProducer};
+
+/// Produces clones of an item, forever.
+///
+/// `stop` drops the item, calling `produce` after that panics.
+#[derive(Debug, Clone)]
+pub struct RepeatProducer<T> {
+    item: Option<T>,
+}
+
+impl<T> RepeatProducer<T> {
+    pub fn new(item: T) -> Self {
+        RepeatProducer { item: Some(item) }
+    }
+}
+
+/// Creates a producer that produces clones of the given item, forever.
+pub fn repeat<T: Clone>(item: T) -> RepeatProducer<T> {
+    RepeatProducer::new(item)
+}
+
+impl<T: Clone> Producer for RepeatProducer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn produce(&mut self) -> Result<T, Infallible> {
+        Ok(self
+            .item
+            .clone()
+            .expect("called produce on a stopped RepeatProducer"))
+    }
+
+    fn slurp(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), Infallible> {
+        self.item = None;
+        Ok(())
+    }
+}
+
+/// Produces clones of an item a fixed number of times, the last time producing the item itself.
+///
+/// After producing the last item or after `stop` (which drops the item), `produce` keeps
+/// returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct RepeatNProducer<T> {
+    item: Option<T>,
+    remaining: usize,
+}
+
+impl<T> RepeatNProducer<T> {
+    pub fn new(item: T, n: usize) -> Self {
+        RepeatNProducer {
+            item: if n == 0 { None } else { Some(item) },
+            remaining: n,
+        }
+    }
+
+    /// Returns how many more items will be produced.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+/// Creates a producer that produces the given item `n` times.
+pub fn repeat_n<T: Clone>(item: T, n: usize) -> RepeatNProducer<T> {
+    RepeatNProducer::new(item, n)
+}
+
+impl<T: Clone> Producer for RepeatNProducer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        match self.remaining {
+            0 => Err(()),
+            1 => {
+                self.remaining = 0;
+                self.item.take().ok_or(())
+            }
+            _ => {
+                self.remaining -= 1;
+                self.item.clone().ok_or(())
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.item = None;
+        self.remaining = 0;
+        Ok(())
+    }
+}
+
+impl<T: Clone> SizedProducer for RepeatNProducer<T> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Produces the results of calling a closure, forever.
+///
+/// `stop` drops the closure, calling `produce` after that panics.
+#[derive(Debug, Clone)]
+pub struct RepeatWithProducer<F> {
+    f: Option<F>,
+}
+
+impl<F> RepeatWithProducer<F> {
+    pub fn new(f: F) -> Self {
+        RepeatWithProducer { f: Some(f) }
+    }
+}
+
+/// Creates a producer that produces the results of calling the given closure, forever.
+pub fn repeat_with<T, F: FnMut() -> T>(f: F) -> RepeatWithProducer<F> {
+    RepeatWithProducer::new(f)
+}
+
+impl<T, F: FnMut() -> T> Producer for RepeatWithProducer<F> {
+    type Item = T;
+    type Ex = ();
+    type In = Infallible;
+
+    fn produce(&mut self) -> Result<T, Infallible> {
+        let f = self
+            .f
+            .as_mut()
+            .expect("called produce on a stopped RepeatWithProducer");
+        Ok(f())
+    }
+
+    fn slurp(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), Infallible> {
+        self.f = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use crate::producer::Producer;
+
+/// Produces the `Ok` value of a result, or performs an internal state change carrying the `Err`
+/// value.
+///
+/// Producing from an `Ok(t)` yields `t`, producing from an `Err(e)` results in `Err(Some(e))`.
+/// After that or after `stop`, `produce` keeps returning `Err(None)`. A plain `Result` can not
+/// implement `Producer` itself since it has no state to return to once its value has been moved
+/// out.
+#[derive(Debug, Clone)]
+pub struct ResultProducer<T, E> {
+    result: Option<Result<T, E>>,
+}
+
+impl<T, E> ResultProducer<T, E> {
+    pub fn new(result: Result<T, E>) -> Self {
+        ResultProducer {
+            result: Some(result),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for ResultProducer<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        ResultProducer::new(result)
+    }
+}
+
+impl<T, E> Producer for ResultProducer<T, E> {
+    type Item = T;
+    type Ex = ();
+    type In = Option<E>;
+
+    fn produce(&mut self) -> Result<T, Option<E>> {
+        match self.result.take() {
+            Some(Ok(item)) => Ok(item),
+            Some(Err(e)) => Err(Some(e)),
+            None => Err(None),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Option<E>> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), Option<E>> {
+        self.result = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::producer::Producer;
+
+/// Produces the items of the wrapped producer, replacing it with a fresh clone of the original and
+/// trying again when it performs an internal state change that the closure deems transient.
+///
+/// The clone is taken on construction. If the closure returns `false` for an internal state
+/// change, or if `max_retries` consecutive attempts have failed, the internal state change is
+/// passed on. The retry count is reset whenever an item is produced. `slurp` and `stop` are
+/// forwarded to the current producer.
+///
+/// Since every retry starts over from the original producer, this only makes sense for producers
+/// whose clones are independent and idempotent, i.e. that produce the same items again without
+/// side effects. For producers that share state between clones (such as handles to a network
+/// buffer), items may be skipped or duplicated.
+#[derive(Debug, Clone)]
+pub struct RetryProducer<P, F> {
+    current: P,
+    original: P,
+    f: F,
+    max_retries: usize,
+}
+
+impl<P: Clone, F> RetryProducer<P, F> {
+    pub fn new(producer: P, max_retries: usize, f: F) -> Self {
+        RetryProducer {
+            original: producer.clone(),
+            current: producer,
+            f,
+            max_retries,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.current
+    }
+}
+
+impl<P, F> Producer for RetryProducer<P, F>
+where
+    P: Producer + Clone,
+    F: FnMut(&P::In) -> bool,
+{
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        let mut retries = 0;
+        loop {
+            match self.current.produce() {
+                Ok(item) => return Ok(item),
+                Err(e) => {
+                    if retries == self.max_retries || !(self.f)(&e) {
+                        return Err(e);
+                    }
+                    retries += 1;
+                    self.current = self.original.clone();
+                }
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.current.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.current.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+
+    use crate::producer::unfold;
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let calls = Cell::new(0);
+        let inner = unfold((), |_: &mut ()| -> Result<u8, &str> {
+            calls.set(calls.get() + 1);
+            Err("transient")
+        });
+        let mut producer = RetryProducer::new(inner, 2, |_: &&str| true);
+
+        assert_eq!(producer.produce(), Err("transient"));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn passes_on_non_transient_state_changes() {
+        let calls = Cell::new(0);
+        let inner = unfold((), |_: &mut ()| -> Result<u8, &str> {
+            calls.set(calls.get() + 1);
+            Err("fatal")
+        });
+        let mut producer = RetryProducer::new(inner, 2, |e: &&str| *e == "transient");
+
+        assert_eq!(producer.produce(), Err("fatal"));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn retry_count_resets_after_item() {
+        let outcomes = [
+            Err("transient"),
+            Ok(1),
+            Err("transient"),
+            Err("transient"),
+            Ok(2),
+        ];
+        let calls = Cell::new(0);
+        let inner = unfold((), |_: &mut ()| {
+            let outcome = outcomes[calls.get()];
+            calls.set(calls.get() + 1);
+            outcome
+        });
+        let mut producer = RetryProducer::new(inner, 2, |_: &&str| true);
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+    }
+
+    #[test]
+    fn retries_start_from_original() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::StateChange("transient"),
+        ]);
+        let mut producer = RetryProducer::new(inner, 1, |_: &&str| true);
+
+        assert_eq!(producer.produce(), Ok(1));
+        // The fresh clone starts over from the beginning.
+        assert_eq!(producer.into_inner().log(), &[ProducerCall::Produce]);
+    }
+}
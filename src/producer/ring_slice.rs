@@ -0,0 +1,213 @@
+use core::cmp;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+
+use loaf::Loaf;
+
+use crate::producer::{Producer, ProducerFrom, ProducerTo, SliceEnd};
+
+/// The error returned when creating a [`RingSliceProducer`] from an inconsistent region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RingSliceError {
+    /// The read offset does not lie within the slice.
+    OffsetOutOfBounds,
+    /// The length of the readable region is greater than the length of the slice.
+    LengthTooLarge,
+}
+
+/// Produces clones of the items of a region of a slice that may wrap around the end of the slice.
+///
+/// The region starts at a read offset and continues at the start of the slice once the end has
+/// been reached. The bulk methods hand out at most the contiguous run up to the wrap point per
+/// call. After producing the last item of the region or after `stop` has been called, `produce`
+/// keeps returning `Err(SliceEnd)`.
+#[derive(Debug, Clone)]
+pub struct RingSliceProducer<'a, T> {
+    slice: &'a [T],
+    offset: usize,
+    len: usize,
+}
+
+impl<'a, T> RingSliceProducer<'a, T> {
+    /// Creates a producer for the `len` items starting at `offset`, wrapping around the end of
+    /// the slice.
+    ///
+    /// The offset must be less than the length of the slice (or zero for an empty slice), and
+    /// `len` must not exceed the length of the slice.
+    pub fn new(slice: &'a [T], offset: usize, len: usize) -> Result<Self, RingSliceError> {
+        if offset >= slice.len() && !(offset == 0 && slice.is_empty()) {
+            return Err(RingSliceError::OffsetOutOfBounds);
+        }
+        if len > slice.len() {
+            return Err(RingSliceError::LengthTooLarge);
+        }
+        Ok(RingSliceProducer { slice, offset, len })
+    }
+
+    /// Returns the offset in the slice of the next item to be produced.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns how many items remain to be produced.
+    pub fn remaining(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the items that have not been produced yet as two slices: the run up to the wrap
+    /// point, and the run from the start of the slice.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        let first = self.contiguous();
+        (
+            &self.slice[self.offset..self.offset + first],
+            &self.slice[..self.len - first],
+        )
+    }
+
+    fn contiguous(&self) -> usize {
+        cmp::min(self.len, self.slice.len() - self.offset)
+    }
+
+    fn advance(&mut self, amount: usize) {
+        self.len -= amount;
+        self.offset += amount;
+        if self.offset == self.slice.len() {
+            self.offset = 0;
+        }
+    }
+}
+
+impl<'a, T: Clone> Producer for RingSliceProducer<'a, T> {
+    type Item = T;
+    type Ex = ();
+    type In = SliceEnd;
+
+    fn produce(&mut self) -> Result<T, SliceEnd> {
+        if self.len == 0 {
+            return Err(SliceEnd);
+        }
+
+        let item = self.slice[self.offset].clone();
+        self.advance(1);
+        Ok(item)
+    }
+
+    fn produce_many(&mut self, buf: &mut [MaybeUninit<T>]) -> Result<NonZeroUsize, SliceEnd> {
+        assert!(!buf.is_empty(), "called produce_many with an empty buffer");
+        let amount = cmp::min(self.contiguous(), buf.len());
+        if amount == 0 {
+            return Err(SliceEnd);
+        }
+
+        let items = &self.slice[self.offset..self.offset + amount];
+        for (slot, item) in buf.iter_mut().zip(items) {
+            *slot = MaybeUninit::new(item.clone());
+        }
+        self.advance(amount);
+        Ok(unsafe { NonZeroUsize::new_unchecked(amount) })
+    }
+
+    fn slurp(&mut self) -> Result<(), SliceEnd> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), SliceEnd> {
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// The returned pointers stay valid for as long as the slice is borrowed.
+impl<'a, T: Clone> ProducerFrom for RingSliceProducer<'a, T> {
+    fn produce_from(&mut self) -> Result<*const T, SliceEnd> {
+        if self.len == 0 {
+            return Err(SliceEnd);
+        }
+
+        let item: *const T = &self.slice[self.offset];
+        self.advance(1);
+        Ok(item)
+    }
+}
+
+/// Writes clones of as many items as fit into the loaf, up to the wrap point.
+impl<'a, T: Clone> ProducerTo for RingSliceProducer<'a, T> {
+    unsafe fn produce_to(
+        &mut self,
+        to: *mut Loaf<MaybeUninit<T>>,
+    ) -> Result<NonZeroUsize, SliceEnd> {
+        let amount = cmp::min(self.contiguous(), (*to).len());
+        if amount == 0 {
+            return Err(SliceEnd);
+        }
+
+        let items = &self.slice[self.offset..self.offset + amount];
+        for (slot, item) in (*to).as_mut_slice().iter_mut().zip(items) {
+            slot.write(item.clone());
+        }
+        self.advance(amount);
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    const ITEMS: [u8; 5] = [0, 1, 2, 3, 4];
+
+    #[test]
+    fn constructor_errors() {
+        assert_eq!(
+            RingSliceProducer::new(&ITEMS, 5, 0).err(),
+            Some(RingSliceError::OffsetOutOfBounds)
+        );
+        assert_eq!(
+            RingSliceProducer::new(&ITEMS, 0, 6).err(),
+            Some(RingSliceError::LengthTooLarge)
+        );
+        assert_eq!(
+            RingSliceProducer::<u8>::new(&[], 1, 0).err(),
+            Some(RingSliceError::OffsetOutOfBounds)
+        );
+        assert!(RingSliceProducer::<u8>::new(&[], 0, 0).is_ok());
+        assert!(RingSliceProducer::new(&ITEMS, 4, 5).is_ok());
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut producer = RingSliceProducer::new(&ITEMS, 3, 4).unwrap();
+        assert_eq!(producer.as_slices(), (&ITEMS[3..], &ITEMS[..2]));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Ok(4));
+        assert_eq!(producer.offset(), 0);
+        assert_eq!(producer.produce(), Ok(0));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.remaining(), 0);
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    #[test]
+    fn produce_many_stops_at_wrap_point() {
+        let mut producer = RingSliceProducer::new(&ITEMS, 3, 5).unwrap();
+        let mut buf = [MaybeUninit::uninit(); 8];
+        let mut runs = Vec::new();
+        while let Ok(amount) = producer.produce_many(&mut buf) {
+            let run: Vec<u8> = buf[..amount.get()]
+                .iter()
+                .map(|slot| unsafe { slot.assume_init() })
+                .collect();
+            runs.push(run);
+        }
+        assert_eq!(runs, [&[3, 4][..], &[0, 1, 2][..]]);
+    }
+
+    #[test]
+    fn stop_empties_region() {
+        let mut producer = RingSliceProducer::new(&ITEMS, 1, 3).unwrap();
+        assert_eq!(producer.stop(()), Ok(()));
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+}
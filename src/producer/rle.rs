@@ -0,0 +1,134 @@
+use core::convert::TryInto;
+
+use crate::producer::Producer;
+
+/// The internal state change of a [`RleDecodeProducer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RleDecodeError<I, E> {
+    /// The wrapped producer performed an internal state change.
+    Inner(I),
+    /// A count cannot be converted into a `usize`, for example because it is too large. The run
+    /// has been skipped, so decoding can continue with the next pair.
+    InvalidCount(E),
+}
+
+/// Expands the `(item, count)` pairs of the wrapped producer into `count` copies of `item`.
+///
+/// Runs are expanded lazily, one item per call to `produce`, and runs of length zero are skipped.
+/// An internal state change of the wrapped producer is only passed on once the current run has
+/// been produced completely. The last item of each run is the original item, all others are
+/// clones. The count can be of any type that can be converted into a `usize`, such as `u8` for
+/// byte streams or `u32` and `u64` for formats with wider counts.
+#[derive(Debug, Clone)]
+pub struct RleDecodeProducer<P, T> {
+    producer: P,
+    item: Option<T>,
+    remaining: usize,
+}
+
+impl<P, T> RleDecodeProducer<P, T> {
+    pub fn new(producer: P) -> Self {
+        RleDecodeProducer {
+            producer,
+            item: None,
+            remaining: 0,
+        }
+    }
+
+    /// Returns how many more items of the current run will be produced.
+    pub fn remaining_in_run(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns the wrapped producer. The rest of the current run is lost.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P, T, C> Producer for RleDecodeProducer<P, T>
+where
+    P: Producer<Item = (T, C)>,
+    T: Clone,
+    C: TryInto<usize>,
+{
+    type Item = T;
+    type Ex = P::Ex;
+    type In = RleDecodeError<P::In, C::Error>;
+
+    fn produce(&mut self) -> Result<T, Self::In> {
+        while self.remaining == 0 {
+            let (item, count) = self.producer.produce().map_err(RleDecodeError::Inner)?;
+            self.remaining = count.try_into().map_err(RleDecodeError::InvalidCount)?;
+            self.item = Some(item);
+        }
+
+        self.remaining -= 1;
+        let item = if self.remaining == 0 {
+            self.item.take()
+        } else {
+            self.item.clone()
+        };
+        Ok(item.expect("a run is in progress"))
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(RleDecodeError::Inner)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.item = None;
+        self.remaining = 0;
+        self.producer.stop(reason).map_err(RleDecodeError::Inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+
+    #[test]
+    fn expands_runs() {
+        let runs = [('a', 2u8), ('b', 0), ('c', 1)];
+        let mut producer = RleDecodeProducer::new(SliceProducer::new(&runs));
+
+        let mut items = Vec::new();
+        let end = loop {
+            match producer.produce() {
+                Ok(item) => items.push(item),
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(items, ['a', 'a', 'c']);
+        assert_eq!(end, RleDecodeError::Inner(SliceEnd));
+    }
+
+    #[test]
+    fn wide_counts() {
+        let runs = [(1, 3u32), (2, 1)];
+        let mut producer = RleDecodeProducer::new(SliceProducer::new(&runs));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.remaining_in_run(), 2);
+
+        let runs = [(1, 2u64)];
+        let mut producer = RleDecodeProducer::new(SliceProducer::new(&runs));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Err(RleDecodeError::Inner(SliceEnd)));
+    }
+
+    #[test]
+    fn count_too_large() {
+        let runs = [(1, -1i8), (2, 1)];
+        let mut producer = RleDecodeProducer::new(SliceProducer::new(&runs));
+        assert!(matches!(
+            producer.produce(),
+            Err(RleDecodeError::InvalidCount(_))
+        ));
+        assert_eq!(producer.produce(), Ok(2));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::producer::Producer;
+
+/// Maps the items of the wrapped producer with a closure that has access to a mutable state.
+///
+/// When the closure returns `None`, an internal state change with `P::In::default()` is
+/// performed, just as if the wrapped producer had been exhausted.
+#[derive(Debug, Clone)]
+pub struct ScanProducer<P, S, F> {
+    producer: P,
+    state: S,
+    f: F,
+}
+
+impl<P, S, F> ScanProducer<P, S, F> {
+    pub fn new(producer: P, state: S, f: F) -> Self {
+        ScanProducer { producer, state, f }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<B, P, S, F> Producer for ScanProducer<P, S, F>
+where
+    P: Producer,
+    P::In: Default,
+    F: FnMut(&mut S, P::Item) -> Option<B>,
+{
+    type Item = B;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<B, P::In> {
+        let item = self.producer.produce()?;
+        (self.f)(&mut self.state, item).ok_or_else(P::In::default)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn running_sum() {
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+        ]);
+        let mut producer = ScanProducer::new(inner, 0, |sum: &mut u8, x| {
+            *sum += x;
+            Some(*sum)
+        });
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Ok(6));
+        assert_eq!(*producer.state(), 6);
+    }
+
+    #[test]
+    fn early_termination() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+        ]);
+        let mut producer = ScanProducer::new(inner, 0, |sum: &mut u8, x| {
+            *sum += x;
+            if *sum > 2 {
+                None
+            } else {
+                Some(*sum)
+            }
+        });
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Err(""));
+        assert_eq!(*producer.state(), 3);
+    }
+
+    #[test]
+    fn forwards_state_changes_without_calling_closure() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::StateChange("in"),
+            ProduceOutcome::Yield(4),
+        ]);
+        let mut calls = 0;
+        let mut producer = ScanProducer::new(inner, (), |_: &mut (), x: u8| {
+            calls += 1;
+            Some(x)
+        });
+
+        assert_eq!(producer.produce(), Err("in"));
+        assert_eq!(producer.produce(), Ok(4));
+        drop(producer);
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::producer::Producer;
+
+/// Drops the first `n` items of the wrapped producer, then produces all remaining items.
+///
+/// The items are skipped lazily, on the first call to `produce`.
+#[derive(Debug, Clone)]
+pub struct SkipProducer<P> {
+    producer: P,
+    n: usize,
+}
+
+impl<P> SkipProducer<P> {
+    pub fn new(producer: P, n: usize) -> Self {
+        SkipProducer { producer, n }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer> Producer for SkipProducer<P> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        while self.n > 0 {
+            self.producer.produce()?;
+            self.n -= 1;
+        }
+        self.producer.produce()
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    #[test]
+    fn skipped_prefix_is_never_produced() {
+        let mut producer = SkipProducer::new(SliceProducer::new(&[0, 1, 2, 3, 4]), 3);
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Ok(4));
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    #[test]
+    fn skipping_past_the_end() {
+        let mut producer = SkipProducer::new(SliceProducer::new(&[0, 1]), 5);
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+
+    fn script() -> ScriptedProducer<u8, ()> {
+        ScriptedProducer::new([
+            ProduceOutcome::Yield(0),
+            ProduceOutcome::StateChange(()),
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+        ])
+    }
+
+    #[test]
+    fn slurp_does_not_skip() {
+        let mut producer = SkipProducer::new(script(), 2);
+        assert_eq!(producer.slurp(), Ok(()));
+        assert_eq!(producer.into_inner().log(), [ProducerCall::Slurp]);
+    }
+
+    #[test]
+    fn resumes_skipping_after_state_change() {
+        let mut producer = SkipProducer::new(script(), 2);
+        assert_eq!(producer.produce(), Err(()));
+        // The item skipped before the internal state change stays skipped.
+        assert_eq!(producer.produce(), Ok(2));
+    }
+}
@@ -0,0 +1,217 @@
+use core::cmp;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+
+use loaf::Loaf;
+
+use crate::producer::{Producer, ProducerFrom, ProducerTo, SizedProducer};
+
+/// The internal state change of a [`SliceProducer`]: there are no more items in the slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SliceEnd;
+
+/// Produces clones of the items of a shared slice.
+///
+/// After producing the last item or after `stop` has been called, `produce` keeps returning
+/// `Err(SliceEnd)`.
+#[derive(Debug, Clone)]
+pub struct SliceProducer<'a, T> {
+    slice: &'a [T],
+    index: usize,
+    stopped: bool,
+}
+
+impl<'a, T> SliceProducer<'a, T> {
+    pub fn new(slice: &'a [T]) -> Self {
+        SliceProducer {
+            slice,
+            index: 0,
+            stopped: false,
+        }
+    }
+
+    /// Returns how many items remain to be produced.
+    pub fn remaining(&self) -> usize {
+        if self.stopped {
+            0
+        } else {
+            self.slice.len() - self.index
+        }
+    }
+
+    /// Returns the items that have not been produced yet, regardless of whether `stop` has been
+    /// called.
+    pub fn remaining_slice(&self) -> &'a [T] {
+        &self.slice[self.index..]
+    }
+
+    /// Consumes the producer, returning the items that have not been produced.
+    pub fn into_remaining_slice(self) -> &'a [T] {
+        self.remaining_slice()
+    }
+}
+
+impl<'a, T: Clone> Producer for SliceProducer<'a, T> {
+    type Item = T;
+    type Ex = ();
+    type In = SliceEnd;
+
+    fn produce(&mut self) -> Result<T, SliceEnd> {
+        if self.stopped {
+            return Err(SliceEnd);
+        }
+
+        match self.slice.get(self.index) {
+            Some(item) => {
+                self.index += 1;
+                Ok(item.clone())
+            }
+            None => Err(SliceEnd),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), SliceEnd> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), SliceEnd> {
+        self.stopped = true;
+        Ok(())
+    }
+}
+
+/// Produces clones of the items of the slice by splitting them off its front.
+///
+/// Producing from the empty slice results in `Err(())`, so does any call after that.
+impl<T: Clone> Producer for &[T] {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        match self.split_first() {
+            Some((first, rest)) => {
+                *self = rest;
+                Ok(first.clone())
+            }
+            None => Err(()),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+impl<T: Clone> SizedProducer for &[T] {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+/// The returned pointers stay valid for as long as the slice is borrowed.
+impl<T: Clone> ProducerFrom for &[T] {
+    fn produce_from(&mut self) -> Result<*const T, ()> {
+        match self.split_first() {
+            Some((first, rest)) => {
+                *self = rest;
+                Ok(first)
+            }
+            None => Err(()),
+        }
+    }
+}
+
+/// Writes clones of as many items as fit into the loaf.
+impl<T: Clone> ProducerTo for &[T] {
+    unsafe fn produce_to(&mut self, to: *mut Loaf<MaybeUninit<T>>) -> Result<NonZeroUsize, ()> {
+        let amount = cmp::min(self.len(), (*to).len());
+        if amount == 0 {
+            return Err(());
+        }
+
+        let (produced, rest) = self.split_at(amount);
+        for (slot, item) in (*to).as_mut_slice().iter_mut().zip(produced) {
+            slot.write(item.clone());
+        }
+        *self = rest;
+        Ok(NonZeroUsize::new_unchecked(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+    use core::ptr;
+
+    const ITEMS: [u16; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+    fn drain_single(mut producer: &[u16]) -> Vec<u16> {
+        let mut items = Vec::new();
+        while let Ok(item) = producer.produce() {
+            items.push(item);
+        }
+        items
+    }
+
+    fn drain_many(mut producer: &[u16], chunk: usize) -> Vec<u16> {
+        let mut items = Vec::new();
+        let mut buf = [MaybeUninit::uninit(); 8];
+        while let Ok(amount) = producer.produce_many(&mut buf[..chunk]) {
+            for slot in &buf[..amount.get()] {
+                items.push(unsafe { slot.assume_init() });
+            }
+        }
+        items
+    }
+
+    fn drain_to(mut producer: &[u16], chunk: usize) -> Vec<u16> {
+        let mut items = Vec::new();
+        let mut buf = [MaybeUninit::uninit(); 8];
+        loop {
+            // A loaf of `chunk` slots is a slice of its `chunk - 1` trailing slots.
+            let to = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr(), chunk - 1)
+                as *mut Loaf<MaybeUninit<u16>>;
+            match unsafe { producer.produce_to(to) } {
+                Ok(amount) => {
+                    for slot in &buf[..amount.get()] {
+                        items.push(unsafe { slot.assume_init() });
+                    }
+                }
+                Err(()) => return items,
+            }
+        }
+    }
+
+    #[test]
+    fn bulk_and_single_paths_agree() {
+        for len in 0..=ITEMS.len() {
+            let slice = &ITEMS[..len];
+            let single = drain_single(slice);
+            assert_eq!(single, slice);
+            for chunk in 1..=8 {
+                assert_eq!(drain_many(slice, chunk), single);
+                assert_eq!(drain_to(slice, chunk), single);
+            }
+        }
+    }
+
+    #[test]
+    fn produce_to_1_and_slurp_produce_to_1() {
+        let mut producer = &ITEMS[..2];
+        let mut slot = MaybeUninit::uninit();
+        unsafe {
+            assert_eq!(producer.produce_to_1(&mut slot), Ok(()));
+            assert_eq!(slot.assume_init(), 1);
+            assert_eq!(producer.slurp_produce_to_1(&mut slot), Ok(()));
+            assert_eq!(slot.assume_init(), 2);
+            assert_eq!(producer.produce_to_1(&mut slot), Err(()));
+        }
+    }
+}
@@ -0,0 +1,297 @@
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::producer::Producer;
+
+/// The internal state change of a [`SplitProducer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplitIn<I> {
+    /// A record has been completed, the next item belongs to the next record.
+    RecordEnd,
+    /// The wrapped producer performed an internal state change.
+    Inner(I),
+}
+
+/// The internal state change of [`SplitProducer::produce_into`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplitIntoError<I> {
+    /// The buffer has been filled with the next items of the current record before reaching its
+    /// end. The next call continues with the rest of the record.
+    BufferFull,
+    /// The wrapped producer performed an internal state change between two records.
+    Inner(I),
+}
+
+/// Produces the items of the wrapped producer, signalling the end of each record terminated by a
+/// delimiter with `Err(SplitIn::RecordEnd)` instead of producing the delimiter.
+///
+/// Consecutive delimiters result in empty records. If the wrapped producer performs an internal
+/// state change in the middle of a record, `Err(SplitIn::RecordEnd)` is returned first, and the
+/// internal state change of the wrapped producer is passed on by the next call to `produce`.
+/// After `Err(SplitIn::RecordEnd)`, the producer can be used normally to produce the next record.
+///
+/// Whole records can be read into a reusable buffer without allocating with
+/// [`produce_into`](SplitProducer::produce_into), see [`SplitVecProducer`] for a producer of
+/// owned records.
+#[derive(Debug, Clone)]
+pub struct SplitProducer<P: Producer> {
+    producer: P,
+    delimiter: P::Item,
+    in_record: bool,
+    pending: Option<P::In>,
+}
+
+impl<P: Producer> SplitProducer<P> {
+    pub fn new(producer: P, delimiter: P::Item) -> Self {
+        SplitProducer {
+            producer,
+            delimiter,
+            in_record: false,
+            pending: None,
+        }
+    }
+
+    /// Returns the wrapped producer. A pending internal state change of it is lost.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+/// Creates a producer that splits the items of the given producer into records terminated by
+/// `delimiter`.
+pub fn split<P: Producer>(producer: P, delimiter: P::Item) -> SplitProducer<P>
+where
+    P::Item: PartialEq,
+{
+    SplitProducer::new(producer, delimiter)
+}
+
+impl<P: Producer> SplitProducer<P>
+where
+    P::Item: PartialEq,
+{
+    /// Reads the next record into the start of `buf`, returning its length.
+    ///
+    /// A partial final record is returned like any other record, the internal state change of the
+    /// wrapped producer is passed on by the next call.
+    pub fn produce_into(&mut self, buf: &mut [P::Item]) -> Result<usize, SplitIntoError<P::In>> {
+        for (len, slot) in buf.iter_mut().enumerate() {
+            match self.produce() {
+                Ok(item) => *slot = item,
+                Err(SplitIn::RecordEnd) => return Ok(len),
+                Err(SplitIn::Inner(e)) => return Err(SplitIntoError::Inner(e)),
+            }
+        }
+        Err(SplitIntoError::BufferFull)
+    }
+}
+
+impl<P: Producer> Producer for SplitProducer<P>
+where
+    P::Item: PartialEq,
+{
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = SplitIn<P::In>;
+
+    fn produce(&mut self) -> Result<P::Item, Self::In> {
+        if let Some(e) = self.pending.take() {
+            return Err(SplitIn::Inner(e));
+        }
+
+        match self.producer.produce() {
+            Ok(item) if item == self.delimiter => {
+                self.in_record = false;
+                Err(SplitIn::RecordEnd)
+            }
+            Ok(item) => {
+                self.in_record = true;
+                Ok(item)
+            }
+            Err(e) if self.in_record => {
+                self.in_record = false;
+                self.pending = Some(e);
+                Err(SplitIn::RecordEnd)
+            }
+            Err(e) => Err(SplitIn::Inner(e)),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(SplitIn::Inner)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.producer.stop(reason).map_err(SplitIn::Inner)
+    }
+}
+
+/// Produces the records of the wrapped producer that are terminated by a delimiter, as vectors.
+///
+/// The delimiters are not included in the records, consecutive delimiters result in empty
+/// records. If the wrapped producer performs an internal state change in the middle of a record,
+/// that partial record is produced, and the internal state change is passed on by the next call
+/// to `produce`.
+#[cfg(feature = "alloc")]
+pub struct SplitVecProducer<P: Producer> {
+    split: SplitProducer<P>,
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Producer> SplitVecProducer<P> {
+    pub fn new(producer: P, delimiter: P::Item) -> Self {
+        SplitVecProducer {
+            split: SplitProducer::new(producer, delimiter),
+        }
+    }
+
+    /// Returns the wrapped producer. A pending internal state change of it is lost.
+    pub fn into_inner(self) -> P {
+        self.split.into_inner()
+    }
+}
+
+/// Creates a producer of the records of the given producer that are terminated by `delimiter`.
+#[cfg(feature = "alloc")]
+pub fn split_vec<P: Producer>(producer: P, delimiter: P::Item) -> SplitVecProducer<P>
+where
+    P::Item: PartialEq,
+{
+    SplitVecProducer::new(producer, delimiter)
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Producer> Producer for SplitVecProducer<P>
+where
+    P::Item: PartialEq,
+{
+    type Item = Vec<P::Item>;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<Vec<P::Item>, P::In> {
+        let mut record = Vec::new();
+        loop {
+            match self.split.produce() {
+                Ok(item) => record.push(item),
+                Err(SplitIn::RecordEnd) => return Ok(record),
+                Err(SplitIn::Inner(e)) => return Err(e),
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.split.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.split.producer.stop(reason)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Producer> Clone for SplitVecProducer<P>
+where
+    SplitProducer<P>: Clone,
+{
+    fn clone(&self) -> Self {
+        SplitVecProducer {
+            split: self.split.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: Producer> fmt::Debug for SplitVecProducer<P>
+where
+    SplitProducer<P>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitVecProducer")
+            .field("split", &self.split)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+
+    #[test]
+    fn items_and_record_ends() {
+        let mut producer = split(SliceProducer::new(b"a,,bc"), b',');
+        assert_eq!(producer.produce(), Ok(b'a'));
+        assert_eq!(producer.produce(), Err(SplitIn::RecordEnd));
+        assert_eq!(producer.produce(), Err(SplitIn::RecordEnd));
+        assert_eq!(producer.produce(), Ok(b'b'));
+        assert_eq!(producer.produce(), Ok(b'c'));
+        assert_eq!(producer.produce(), Err(SplitIn::RecordEnd));
+        assert_eq!(producer.produce(), Err(SplitIn::Inner(SliceEnd)));
+    }
+
+    #[test]
+    fn trailing_delimiter_has_no_partial_record() {
+        let mut producer = split(SliceProducer::new(b"a,"), b',');
+        assert_eq!(producer.produce(), Ok(b'a'));
+        assert_eq!(producer.produce(), Err(SplitIn::RecordEnd));
+        assert_eq!(producer.produce(), Err(SplitIn::Inner(SliceEnd)));
+    }
+
+    #[test]
+    fn produce_into_reuses_buffer() {
+        let mut producer = split(SliceProducer::new(b"ab,,c,def"), b',');
+        let mut buf = [0; 4];
+        let mut records = Vec::new();
+        let end = loop {
+            match producer.produce_into(&mut buf) {
+                Ok(len) => records.push(buf[..len].to_vec()),
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(records, [&b"ab"[..], b"", b"c", b"def"]);
+        assert_eq!(end, SplitIntoError::Inner(SliceEnd));
+    }
+
+    #[test]
+    fn produce_into_buffer_full() {
+        let mut producer = split(SliceProducer::new(b"abcde,f"), b',');
+        let mut buf = [0; 2];
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(SplitIntoError::BufferFull)
+        );
+        assert_eq!(&buf, b"ab");
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(SplitIntoError::BufferFull)
+        );
+        assert_eq!(&buf, b"cd");
+        assert_eq!(producer.produce_into(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"e");
+        assert_eq!(producer.produce_into(&mut buf), Ok(1));
+        assert_eq!(&buf[..1], b"f");
+        assert_eq!(
+            producer.produce_into(&mut buf),
+            Err(SplitIntoError::Inner(SliceEnd))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_records() {
+        let mut producer = split_vec(SliceProducer::new(b",a,,bc"), b',');
+        assert_eq!(producer.produce(), Ok(Vec::new()));
+        assert_eq!(producer.produce(), Ok(b"a".to_vec()));
+        assert_eq!(producer.produce(), Ok(Vec::new()));
+        // The partial final record comes before the end of the wrapped producer.
+        assert_eq!(producer.produce(), Ok(b"bc".to_vec()));
+        assert_eq!(producer.produce(), Err(SliceEnd));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::producer::Producer;
+
+/// Produces the `char`s of a string slice.
+///
+/// After producing the last `char`, `produce` keeps returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct CharProducer<'a> {
+    remaining: &'a str,
+}
+
+impl<'a> CharProducer<'a> {
+    pub fn new(s: &'a str) -> Self {
+        CharProducer { remaining: s }
+    }
+
+    /// Returns the part of the string that has not been produced yet.
+    pub fn remaining_str(&self) -> &'a str {
+        self.remaining
+    }
+}
+
+impl<'a> Producer for CharProducer<'a> {
+    type Item = char;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<char, ()> {
+        let mut chars = self.remaining.chars();
+        let c = chars.next().ok_or(())?;
+        self.remaining = chars.as_str();
+        Ok(c)
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Produces the `char`s of a string slice together with their byte offsets.
+///
+/// After producing the last `char`, `produce` keeps returning `Err(len)`, where `len` is the byte
+/// length of the string, i.e., the offset at which the input ended.
+#[derive(Debug, Clone)]
+pub struct CharIndicesProducer<'a> {
+    remaining: &'a str,
+    offset: usize,
+}
+
+impl<'a> CharIndicesProducer<'a> {
+    pub fn new(s: &'a str) -> Self {
+        CharIndicesProducer {
+            remaining: s,
+            offset: 0,
+        }
+    }
+
+    /// Returns the byte offset of the next `char`.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the part of the string that has not been produced yet.
+    pub fn remaining_str(&self) -> &'a str {
+        self.remaining
+    }
+}
+
+impl<'a> Producer for CharIndicesProducer<'a> {
+    type Item = (usize, char);
+    type Ex = ();
+    type In = usize;
+
+    fn produce(&mut self) -> Result<(usize, char), usize> {
+        let mut chars = self.remaining.chars();
+        let c = chars.next().ok_or(self.offset)?;
+        let offset = self.offset;
+        self.offset += c.len_utf8();
+        self.remaining = chars.as_str();
+        Ok((offset, c))
+    }
+
+    fn slurp(&mut self) -> Result<(), usize> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), usize> {
+        Ok(())
+    }
+}
+
+/// Produces the lines of a string slice, without their line terminators.
+///
+/// Lines are terminated by either `\n` or `\r\n`. The terminator of the last line is optional, so
+/// a string that ends with a terminator does not produce a trailing empty line. After producing the
+/// last line, `produce` keeps returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct LinesProducer<'a> {
+    remaining: &'a str,
+}
+
+impl<'a> LinesProducer<'a> {
+    pub fn new(s: &'a str) -> Self {
+        LinesProducer { remaining: s }
+    }
+
+    /// Returns the part of the string that has not been produced yet.
+    pub fn remaining_str(&self) -> &'a str {
+        self.remaining
+    }
+}
+
+impl<'a> Producer for LinesProducer<'a> {
+    type Item = &'a str;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<&'a str, ()> {
+        if self.remaining.is_empty() {
+            return Err(());
+        }
+
+        match self.remaining.find('\n') {
+            Some(end) => {
+                let line = &self.remaining[..end];
+                self.remaining = &self.remaining[end + 1..];
+                Ok(line.strip_suffix('\r').unwrap_or(line))
+            }
+            None => {
+                let line = self.remaining;
+                self.remaining = "";
+                Ok(line)
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checks that the producer yields the same lines as `str::lines`.
+    fn assert_lines(s: &str, expected: &[&str]) {
+        let mut producer = LinesProducer::new(s);
+        for (line, expected_line) in s.lines().zip(expected) {
+            assert_eq!(line, *expected_line);
+            assert_eq!(producer.produce(), Ok(line));
+        }
+        assert_eq!(s.lines().count(), expected.len());
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn lines_empty_input() {
+        assert_lines("", &[]);
+    }
+
+    #[test]
+    fn lines_crlf() {
+        assert_lines("a\r\nb\r\n", &["a", "b"]);
+        assert_lines("a\r\n\r\nb", &["a", "", "b"]);
+        assert_lines("\r\n", &[""]);
+    }
+
+    #[test]
+    fn lines_mixed_terminators() {
+        assert_lines("a\nb\r\nc", &["a", "b", "c"]);
+        assert_lines("\n\n", &["", ""]);
+        // A lone `\r` is not a terminator.
+        assert_lines("a\rb\n", &["a\rb"]);
+    }
+
+    #[test]
+    fn chars_non_ascii() {
+        let s = "aé€😀";
+        let mut producer = CharProducer::new(s);
+        for c in ['a', 'é', '€', '😀'] {
+            assert_eq!(producer.produce(), Ok(c));
+        }
+        assert_eq!(producer.remaining_str(), "");
+        assert_eq!(producer.produce(), Err(()));
+    }
+
+    #[test]
+    fn char_indices_non_ascii() {
+        let s = "aé€😀";
+        let mut producer = CharIndicesProducer::new(s);
+        for (offset, c) in s.char_indices() {
+            assert_eq!(producer.produce(), Ok((offset, c)));
+        }
+        assert_eq!(producer.produce(), Err(s.len()));
+        assert_eq!(s.len(), 10);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::producer::Producer;
+
+/// Produces an item and then repeatedly the result of calling a closure on the previous item,
+/// until the closure returns `None`.
+///
+/// After the closure returned `None` or after `stop` (which drops the next item), `produce` keeps
+/// returning `Err(())`.
+#[derive(Debug, Clone)]
+pub struct SuccessorsProducer<T, F> {
+    next: Option<T>,
+    f: F,
+}
+
+impl<T, F> SuccessorsProducer<T, F> {
+    pub fn new(first: Option<T>, f: F) -> Self {
+        SuccessorsProducer { next: first, f }
+    }
+}
+
+/// Creates a producer that produces `first` (if it is `Some`) and then the successors computed by
+/// the given closure.
+pub fn successors<T, F>(first: Option<T>, f: F) -> SuccessorsProducer<T, F>
+where
+    F: FnMut(&T) -> Option<T>,
+{
+    SuccessorsProducer::new(first, f)
+}
+
+impl<T, F: FnMut(&T) -> Option<T>> Producer for SuccessorsProducer<T, F> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        let item = self.next.take().ok_or(())?;
+        self.next = (self.f)(&item);
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.next = None;
+        Ok(())
+    }
+}
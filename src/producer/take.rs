@@ -0,0 +1,207 @@
+use core::cmp;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+use core::ptr;
+
+use loaf::Loaf;
+
+use crate::producer::{Producer, ProducerTo, SizedProducer};
+
+/// Produces at most a fixed number of items of the wrapped producer, then performs an internal
+/// state change with a fixed `In` value.
+#[derive(Debug, Clone)]
+pub struct TakeProducer<P: Producer> {
+    producer: P,
+    remaining: usize,
+    end: P::In,
+}
+
+impl<P: Producer> TakeProducer<P> {
+    /// Produces at most `n` items, then signals `Err(end)`.
+    pub fn with_end(producer: P, n: usize, end: P::In) -> Self {
+        TakeProducer {
+            producer,
+            remaining: n,
+            end,
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer> TakeProducer<P>
+where
+    P::In: Default,
+{
+    /// Produces at most `n` items, then signals `Err(P::In::default())`.
+    pub fn new(producer: P, n: usize) -> Self {
+        TakeProducer::with_end(producer, n, P::In::default())
+    }
+}
+
+impl<P: Producer> Producer for TakeProducer<P>
+where
+    P::In: Clone,
+{
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<P::Item, P::In> {
+        if self.remaining == 0 {
+            return Err(self.end.clone());
+        }
+
+        let item = self.producer.produce()?;
+        self.remaining -= 1;
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+impl<P: SizedProducer> SizedProducer for TakeProducer<P>
+where
+    P::In: Clone,
+{
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.producer.size_hint();
+        let upper = match upper {
+            Some(upper) => cmp::min(upper, self.remaining),
+            None => self.remaining,
+        };
+        (cmp::min(lower, self.remaining), Some(upper))
+    }
+}
+
+impl<P: ProducerTo> ProducerTo for TakeProducer<P>
+where
+    P::In: Clone,
+{
+    unsafe fn produce_to(
+        &mut self,
+        to: *mut Loaf<MaybeUninit<P::Item>>,
+    ) -> Result<NonZeroUsize, P::In> {
+        if self.remaining == 0 {
+            return Err(self.end.clone());
+        }
+
+        let amount = cmp::min((*to).len(), self.remaining);
+        let to = ptr::slice_from_raw_parts_mut(to as *mut MaybeUninit<P::Item>, amount - 1)
+            as *mut Loaf<MaybeUninit<P::Item>>;
+        let produced = self.producer.produce_to(to)?;
+        self.remaining -= produced.get();
+        Ok(produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::testing::{ProduceOutcome, ProducerCall, ScriptedProducer};
+
+    const ITEMS: [u16; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+    fn drain_single(mut producer: TakeProducer<&[u16]>) -> (Vec<u16>, ()) {
+        let mut items = Vec::new();
+        loop {
+            match producer.produce() {
+                Ok(item) => items.push(item),
+                Err(e) => return (items, e),
+            }
+        }
+    }
+
+    fn drain_to(mut producer: TakeProducer<&[u16]>, chunk: usize) -> (Vec<u16>, ()) {
+        let mut items = Vec::new();
+        let mut buf = [MaybeUninit::uninit(); 8];
+        loop {
+            // A loaf of `chunk` slots is a slice of its `chunk - 1` trailing slots.
+            let to = ptr::slice_from_raw_parts_mut(buf.as_mut_ptr(), chunk - 1)
+                as *mut Loaf<MaybeUninit<u16>>;
+            match unsafe { producer.produce_to(to) } {
+                Ok(amount) => {
+                    assert!(amount.get() <= chunk);
+                    for slot in &buf[..amount.get()] {
+                        items.push(unsafe { slot.assume_init() });
+                    }
+                }
+                Err(e) => return (items, e),
+            }
+        }
+    }
+
+    #[test]
+    fn produce_to_agrees_with_produce() {
+        for n in 0..=ITEMS.len() + 1 {
+            let single = drain_single(TakeProducer::new(&ITEMS[..], n));
+            assert_eq!(single.0, ITEMS[..n.min(ITEMS.len())]);
+            for chunk in 1..=8 {
+                assert_eq!(drain_to(TakeProducer::new(&ITEMS[..], n), chunk), single);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_does_not_touch_inner() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([ProduceOutcome::PanicIfCalled]);
+        let mut producer = TakeProducer::new(inner, 0);
+        assert_eq!(producer.produce(), Err(""));
+        assert_eq!(producer.produce(), Err(""));
+        assert_eq!(producer.into_inner().log(), []);
+    }
+
+    #[test]
+    fn with_end() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let mut producer = TakeProducer::with_end(inner, 2, "taken");
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err("taken"));
+        assert_eq!(producer.produce(), Err("taken"));
+    }
+
+    #[test]
+    fn inner_state_change_before_limit() {
+        let inner = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::StateChange("inner"),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let mut producer = TakeProducer::with_end(inner, 2, "taken");
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(producer.produce(), Err("inner"));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Err("taken"));
+    }
+
+    #[test]
+    fn stop_is_forwarded() {
+        let inner = ScriptedProducer::<u8, (), &str>::new([]);
+        let mut producer = TakeProducer::new(inner, 0);
+        assert_eq!(producer.stop("done"), Ok(()));
+        assert_eq!(producer.into_inner().log(), [ProducerCall::Stop("done")]);
+    }
+
+    #[test]
+    fn size_hint_is_capped() {
+        assert_eq!(TakeProducer::new(&ITEMS[..], 3).size_hint(), (3, Some(3)));
+        assert_eq!(TakeProducer::new(&ITEMS[..], 10).size_hint(), (7, Some(7)));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::producer::Producer;
+
+/// Produces the results of repeatedly calling a closure on a mutable state.
+///
+/// The closure decides both the items and when an internal state change happens. The state can
+/// be inspected and recovered at any time, including after an internal state change.
+#[derive(Debug, Clone)]
+pub struct UnfoldProducer<St, F> {
+    state: St,
+    f: F,
+}
+
+impl<St, F> UnfoldProducer<St, F> {
+    pub fn new(state: St, f: F) -> Self {
+        UnfoldProducer { state, f }
+    }
+
+    pub fn state(&self) -> &St {
+        &self.state
+    }
+
+    pub fn into_state(self) -> St {
+        self.state
+    }
+}
+
+/// Creates a producer whose `produce` method calls the given closure on the state.
+pub fn unfold<St, T, In, F>(state: St, f: F) -> UnfoldProducer<St, F>
+where
+    F: FnMut(&mut St) -> Result<T, In>,
+{
+    UnfoldProducer::new(state, f)
+}
+
+impl<St, T, In, F> Producer for UnfoldProducer<St, F>
+where
+    F: FnMut(&mut St) -> Result<T, In>,
+{
+    type Item = T;
+    type Ex = ();
+    type In = In;
+
+    fn produce(&mut self) -> Result<T, In> {
+        (self.f)(&mut self.state)
+    }
+
+    fn slurp(&mut self) -> Result<(), In> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), In> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    #[test]
+    fn fibonacci() {
+        let mut producer = unfold((0u8, 1u8), |(a, b): &mut (u8, u8)| -> Result<u8, ()> {
+            let next = a.checked_add(*b).ok_or(())?;
+            let item = *a;
+            *a = *b;
+            *b = next;
+            Ok(item)
+        });
+
+        let mut items = Vec::new();
+        while let Ok(item) = producer.produce() {
+            items.push(item);
+        }
+        assert_eq!(items, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]);
+        assert_eq!(producer.state(), &(144, 233));
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum DecodeEnd {
+        Done,
+        Truncated { partial: u16 },
+    }
+
+    // Decodes big-endian u16 values from a byte slice, keeping the bytes consumed so far as
+    // state so that a truncated final value can be recovered.
+    #[test]
+    fn decoder() {
+        let bytes = [0x01, 0x02, 0xff, 0xff, 0x03];
+        let mut producer = unfold(
+            (&bytes[..], 0u16, 0usize),
+            |(input, partial, pending): &mut (&[u8], u16, usize)| loop {
+                match input.split_first() {
+                    None if *pending == 0 => return Err(DecodeEnd::Done),
+                    None => return Err(DecodeEnd::Truncated { partial: *partial }),
+                    Some((byte, rest)) => {
+                        *input = rest;
+                        *partial = (*partial << 8) | u16::from(*byte);
+                        *pending += 1;
+                        if *pending == 2 {
+                            let value = *partial;
+                            *partial = 0;
+                            *pending = 0;
+                            return Ok(value);
+                        }
+                    }
+                }
+            },
+        );
+
+        assert_eq!(producer.produce(), Ok(0x0102));
+        assert_eq!(producer.produce(), Ok(0xffff));
+        assert_eq!(
+            producer.produce(),
+            Err(DecodeEnd::Truncated { partial: 0x03 })
+        );
+        let (rest, partial, pending) = producer.into_state();
+        assert!(rest.is_empty());
+        assert_eq!((partial, pending), (0x03, 1));
+    }
+}
@@ -0,0 +1,275 @@
+use crate::producer::Producer;
+use crate::util::{is_utf8_continuation, utf8_width};
+
+/// The internal state change of a [`DecodeUtf8Producer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeUtf8Error<I> {
+    /// The bytes starting at the given offset are not valid UTF-8. The invalid bytes have been
+    /// skipped, decoding continues after them.
+    Invalid { offset: usize },
+    /// The wrapped producer performed an internal state change in the middle of a `char`. The
+    /// bytes of the incomplete `char` are kept, so decoding can resume if the wrapped producer
+    /// produces more bytes.
+    Incomplete(I),
+    /// The wrapped producer performed an internal state change between two `char`s.
+    Inner(I),
+}
+
+/// Decodes the UTF-8 bytes of the wrapped producer into `char`s.
+///
+/// Invalid byte sequences result in `Err(DecodeUtf8Error::Invalid)`, or in `U+FFFD` if the
+/// producer was created with [`lossy`](DecodeUtf8Producer::lossy). Invalid sequences are delimited
+/// the same way as in [`core::str::from_utf8`], so a byte that cannot continue a sequence is
+/// decoded as the start of the next one.
+#[derive(Debug, Clone)]
+pub struct DecodeUtf8Producer<P> {
+    producer: P,
+    lossy: bool,
+    // The first `len` bytes of the `char` that is currently being decoded, which is `width` bytes
+    // long in total.
+    buf: [u8; 4],
+    len: usize,
+    width: usize,
+    // A byte that was read but turned out to not belong to the preceding invalid sequence.
+    lookahead: Option<u8>,
+    offset: usize,
+}
+
+impl<P> DecodeUtf8Producer<P> {
+    pub fn new(producer: P) -> Self {
+        DecodeUtf8Producer {
+            producer,
+            lossy: false,
+            buf: [0; 4],
+            len: 0,
+            width: 0,
+            lookahead: None,
+            offset: 0,
+        }
+    }
+
+    /// Creates a producer that produces `U+FFFD REPLACEMENT CHARACTER` for invalid byte sequences
+    /// rather than performing an internal state change.
+    pub fn lossy(producer: P) -> Self {
+        DecodeUtf8Producer {
+            lossy: true,
+            ..DecodeUtf8Producer::new(producer)
+        }
+    }
+
+    /// Returns the offset in the wrapped producer's bytes at which the next `char` starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the wrapped producer. Bytes of an incomplete `char` are lost.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+
+    fn invalid(&mut self, len: usize) -> Result<char, DecodeUtf8Error<P::In>>
+    where
+        P: Producer,
+    {
+        let offset = self.offset;
+        self.offset += len;
+        self.len = 0;
+        if self.lossy {
+            Ok(char::REPLACEMENT_CHARACTER)
+        } else {
+            Err(DecodeUtf8Error::Invalid { offset })
+        }
+    }
+}
+
+impl<P: Producer<Item = u8>> Producer for DecodeUtf8Producer<P> {
+    type Item = char;
+    type Ex = P::Ex;
+    type In = DecodeUtf8Error<P::In>;
+
+    fn produce(&mut self) -> Result<char, Self::In> {
+        if self.len == 0 {
+            let first = match self.lookahead.take() {
+                Some(byte) => byte,
+                None => self.producer.produce().map_err(DecodeUtf8Error::Inner)?,
+            };
+
+            match utf8_width(first) {
+                Some(1) => {
+                    self.offset += 1;
+                    return Ok(first as char);
+                }
+                Some(width) => {
+                    self.buf[0] = first;
+                    self.len = 1;
+                    self.width = width;
+                }
+                None => return self.invalid(1),
+            }
+        }
+
+        while self.len < self.width {
+            let byte = self
+                .producer
+                .produce()
+                .map_err(DecodeUtf8Error::Incomplete)?;
+            if !is_utf8_continuation(&self.buf[..self.len], byte) {
+                self.lookahead = Some(byte);
+                return self.invalid(self.len);
+            }
+            self.buf[self.len] = byte;
+            self.len += 1;
+        }
+
+        // The bytes have been validated above.
+        let c = core::str::from_utf8(&self.buf[..self.width])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        self.offset += self.width;
+        self.len = 0;
+        Ok(c)
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(DecodeUtf8Error::Inner)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.len = 0;
+        self.lookahead = None;
+        self.producer.stop(reason).map_err(DecodeUtf8Error::Inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    // Decodes until the wrapped producer ends, collecting all results including the final one.
+    fn decode(
+        mut producer: DecodeUtf8Producer<SliceProducer<u8>>,
+    ) -> Vec<Result<char, DecodeUtf8Error<SliceEnd>>> {
+        let mut results = Vec::new();
+        loop {
+            let result = producer.produce();
+            let done = matches!(
+                result,
+                Err(DecodeUtf8Error::Inner(_)) | Err(DecodeUtf8Error::Incomplete(_))
+            );
+            results.push(result);
+            if done {
+                return results;
+            }
+        }
+    }
+
+    #[test]
+    fn valid_input() {
+        let input = "aZ~ é€😀!";
+        let results = decode(DecodeUtf8Producer::new(SliceProducer::new(
+            input.as_bytes(),
+        )));
+        let (end, chars) = results.split_last().unwrap();
+        assert_eq!(end, &Err(DecodeUtf8Error::Inner(SliceEnd)));
+        assert!(chars.iter().map(|c| *c.as_ref().unwrap()).eq(input.chars()));
+    }
+
+    #[test]
+    fn offset_tracks_bytes() {
+        let mut producer = DecodeUtf8Producer::new(SliceProducer::new("aé€😀".as_bytes()));
+        let mut offsets = Vec::new();
+        while producer.produce().is_ok() {
+            offsets.push(producer.offset());
+        }
+        assert_eq!(offsets, [1, 3, 6, 10]);
+    }
+
+    #[test]
+    fn invalid_lead_byte() {
+        let results = decode(DecodeUtf8Producer::new(SliceProducer::new(b"ab\xFFc")));
+        assert_eq!(
+            results,
+            [
+                Ok('a'),
+                Ok('b'),
+                Err(DecodeUtf8Error::Invalid { offset: 2 }),
+                Ok('c'),
+                Err(DecodeUtf8Error::Inner(SliceEnd)),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_continuation_byte() {
+        // The `(` cannot continue the sequence, so it is decoded as the start of the next one.
+        let results = decode(DecodeUtf8Producer::new(SliceProducer::new(b"a\xE2\x82(b")));
+        assert_eq!(
+            results,
+            [
+                Ok('a'),
+                Err(DecodeUtf8Error::Invalid { offset: 1 }),
+                Ok('('),
+                Ok('b'),
+                Err(DecodeUtf8Error::Inner(SliceEnd)),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_four_byte_sequence() {
+        let bytes = "😀".as_bytes();
+        for k in 1..4 {
+            let results = decode(DecodeUtf8Producer::new(SliceProducer::new(&bytes[..k])));
+            assert_eq!(
+                results,
+                [Err(DecodeUtf8Error::Incomplete(SliceEnd))],
+                "k = {}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn resumes_after_incomplete() {
+        let bytes = "😀".as_bytes();
+        for k in 1..4 {
+            let mut script = Vec::new();
+            script.extend(bytes[..k].iter().map(|&b| ProduceOutcome::Yield(b)));
+            script.push(ProduceOutcome::StateChange(()));
+            script.extend(bytes[k..].iter().map(|&b| ProduceOutcome::Yield(b)));
+            script.push(ProduceOutcome::StateChange(()));
+            let mut producer = DecodeUtf8Producer::new(ScriptedProducer::<u8, (), ()>::new(script));
+
+            assert_eq!(producer.produce(), Err(DecodeUtf8Error::Incomplete(())));
+            assert_eq!(producer.offset(), 0);
+            assert_eq!(producer.produce(), Ok('😀'));
+            assert_eq!(producer.offset(), 4);
+            assert_eq!(producer.produce(), Err(DecodeUtf8Error::Inner(())));
+        }
+    }
+
+    #[test]
+    fn lossy_resynchronises() {
+        let inputs: [&[u8]; 5] = [
+            b"a\xFFb",
+            b"a\xF0\x9F(b",
+            b"\xE2\x82\xE2\x82\xACz",
+            b"\xC0\x80x",
+            b"\xED\xA0\x80y",
+        ];
+        for input in inputs.iter() {
+            let results = decode(DecodeUtf8Producer::lossy(SliceProducer::new(input)));
+            let (end, chars) = results.split_last().unwrap();
+            assert_eq!(end, &Err(DecodeUtf8Error::Inner(SliceEnd)));
+            let decoded: String = chars.iter().map(|c| c.unwrap()).collect();
+            assert_eq!(decoded, String::from_utf8_lossy(input), "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::producer::Producer;
+
+/// The internal state change of a [`VarintProducer`] or [`ZigzagVarintProducer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VarintError<I> {
+    /// The wrapped producer performed an internal state change between two varints.
+    Inner(I),
+    /// The wrapped producer performed an internal state change in the middle of a varint. The
+    /// bytes that have been read are kept, so decoding can resume if the wrapped producer produces
+    /// more bytes.
+    Truncated(I),
+    /// The varint encodes a value that does not fit into 64 bits.
+    Overflow,
+    /// The varint ends in a zero byte, so a shorter encoding of the same value exists.
+    Overlong,
+}
+
+/// Decodes unsigned LEB128 varints from the bytes of the wrapped producer.
+///
+/// After `Err(Overflow)` or `Err(Overlong)`, the bytes of the invalid varint up to and including
+/// the byte at which the error was detected have been consumed, and decoding continues with the
+/// next byte.
+#[derive(Debug, Clone)]
+pub struct VarintProducer<P> {
+    producer: P,
+    value: u64,
+    // How many bytes of the current varint have been read.
+    len: u32,
+}
+
+impl<P> VarintProducer<P> {
+    pub fn new(producer: P) -> Self {
+        VarintProducer {
+            producer,
+            value: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the wrapped producer. Bytes of an incomplete varint are lost.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P: Producer<Item = u8>> Producer for VarintProducer<P> {
+    type Item = u64;
+    type Ex = P::Ex;
+    type In = VarintError<P::In>;
+
+    fn produce(&mut self) -> Result<u64, Self::In> {
+        loop {
+            let byte = match self.producer.produce() {
+                Ok(byte) => byte,
+                Err(e) if self.len == 0 => return Err(VarintError::Inner(e)),
+                Err(e) => return Err(VarintError::Truncated(e)),
+            };
+
+            let len = self.len;
+            let payload = u64::from(byte & 0x7F);
+            // The tenth byte may only contribute the single remaining bit.
+            if len == 10 || (len == 9 && payload > 1) {
+                self.value = 0;
+                self.len = 0;
+                return Err(VarintError::Overflow);
+            }
+
+            self.value |= payload << (7 * len);
+            self.len += 1;
+
+            if byte & 0x80 == 0 {
+                let value = self.value;
+                self.value = 0;
+                self.len = 0;
+                return if byte == 0 && len > 0 {
+                    Err(VarintError::Overlong)
+                } else {
+                    Ok(value)
+                };
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.producer.slurp().map_err(VarintError::Inner)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.value = 0;
+        self.len = 0;
+        self.producer.stop(reason).map_err(VarintError::Inner)
+    }
+}
+
+/// Decodes zigzag-encoded signed LEB128 varints from the bytes of the wrapped producer.
+///
+/// Behaves like a [`VarintProducer`] otherwise.
+#[derive(Debug, Clone)]
+pub struct ZigzagVarintProducer<P> {
+    varint: VarintProducer<P>,
+}
+
+impl<P> ZigzagVarintProducer<P> {
+    pub fn new(producer: P) -> Self {
+        ZigzagVarintProducer {
+            varint: VarintProducer::new(producer),
+        }
+    }
+
+    /// Returns the wrapped producer. Bytes of an incomplete varint are lost.
+    pub fn into_inner(self) -> P {
+        self.varint.into_inner()
+    }
+}
+
+impl<P: Producer<Item = u8>> Producer for ZigzagVarintProducer<P> {
+    type Item = i64;
+    type Ex = P::Ex;
+    type In = VarintError<P::In>;
+
+    fn produce(&mut self) -> Result<i64, Self::In> {
+        let n = self.varint.produce()?;
+        Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.varint.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Self::In> {
+        self.varint.stop(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::producer::{SliceEnd, SliceProducer};
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    fn varints(bytes: &[u8]) -> VarintProducer<SliceProducer<'_, u8>> {
+        VarintProducer::new(SliceProducer::new(bytes))
+    }
+
+    #[test]
+    fn decodes_valid_varints() {
+        let mut producer = varints(&[
+            0x00, 0x01, 0x7F, 0x80, 0x01, 0xAC, 0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0x01,
+        ]);
+        for expected in [0, 1, 127, 128, 300, u64::MAX] {
+            assert_eq!(producer.produce(), Ok(expected));
+        }
+        assert_eq!(producer.produce(), Err(VarintError::Inner(SliceEnd)));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut producer = varints(&[0x80, 0x80]);
+        assert_eq!(producer.produce(), Err(VarintError::Truncated(SliceEnd)));
+    }
+
+    #[test]
+    fn truncated_varint_resumes() {
+        let mut producer = VarintProducer::new(ScriptedProducer::<u8, ()>::new([
+            ProduceOutcome::Yield(0x80),
+            ProduceOutcome::StateChange(()),
+            ProduceOutcome::Yield(0x01),
+        ]));
+        assert_eq!(producer.produce(), Err(VarintError::Truncated(())));
+        assert_eq!(producer.produce(), Ok(128));
+    }
+
+    #[test]
+    fn overlong() {
+        let mut producer = varints(&[0x80, 0x00, 0x05]);
+        assert_eq!(producer.produce(), Err(VarintError::Overlong));
+        assert_eq!(producer.produce(), Ok(5));
+    }
+
+    #[test]
+    fn overflow_in_tenth_byte() {
+        let mut producer = varints(&[
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02, 0x05,
+        ]);
+        assert_eq!(producer.produce(), Err(VarintError::Overflow));
+        assert_eq!(producer.produce(), Ok(5));
+    }
+
+    #[test]
+    fn overflow_in_eleventh_byte() {
+        let mut producer = varints(&[
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x81, 0x01, 0x05,
+        ]);
+        assert_eq!(producer.produce(), Err(VarintError::Overflow));
+        assert_eq!(producer.produce(), Ok(5));
+    }
+
+    #[test]
+    fn zigzag() {
+        let mut producer = ZigzagVarintProducer::new(SliceProducer::new(&[
+            0x00, 0x01, 0x02, 0x03, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01,
+        ]));
+        for expected in [0, -1, 1, -2, i64::MIN] {
+            assert_eq!(producer.produce(), Ok(expected));
+        }
+        assert_eq!(producer.produce(), Err(VarintError::Inner(SliceEnd)));
+    }
+}
@@ -0,0 +1,108 @@
+use core::fmt;
+use core::mem;
+use core::ptr;
+
+use alloc::vec::Vec;
+
+use crate::producer::Producer;
+
+/// Produces the items of a vector by value, front to back.
+///
+/// After producing the last item or after `stop` (which drops all items that have not been
+/// produced), `produce` keeps returning `Err(())`.
+pub struct VecProducer<T> {
+    // Always has length zero, so that dropping it only releases the memory. The items in
+    // `start..end` are initialized, all others have been moved out or dropped.
+    vec: Vec<T>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> VecProducer<T> {
+    pub fn new(mut vec: Vec<T>) -> Self {
+        let end = vec.len();
+        unsafe { vec.set_len(0) };
+        VecProducer { vec, start: 0, end }
+    }
+
+    /// Returns the items that have not been produced yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            &*ptr::slice_from_raw_parts(self.vec.as_ptr().add(self.start), self.end - self.start)
+        }
+    }
+
+    /// Returns a vector of the items that have not been produced yet, reusing the memory of the
+    /// original vector.
+    pub fn into_remaining(mut self) -> Vec<T> {
+        let remaining = self.end - self.start;
+        let mut vec = mem::take(&mut self.vec);
+        unsafe {
+            let base = vec.as_mut_ptr();
+            ptr::copy(base.add(self.start), base, remaining);
+            vec.set_len(remaining);
+        }
+        self.start = self.end;
+        vec
+    }
+
+    fn drop_remaining(&mut self) {
+        let remaining = self.end - self.start;
+        unsafe {
+            let base = self.vec.as_mut_ptr().add(self.start);
+            self.start = self.end;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base, remaining));
+        }
+    }
+}
+
+impl<T> Producer for VecProducer<T> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        if self.start == self.end {
+            return Err(());
+        }
+
+        let item = unsafe { ptr::read(self.vec.as_ptr().add(self.start)) };
+        self.start += 1;
+        Ok(item)
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        self.drop_remaining();
+        Ok(())
+    }
+}
+
+impl<T> Drop for VecProducer<T> {
+    fn drop(&mut self) {
+        self.drop_remaining();
+    }
+}
+
+impl<T> From<Vec<T>> for VecProducer<T> {
+    fn from(vec: Vec<T>) -> Self {
+        VecProducer::new(vec)
+    }
+}
+
+impl<T: Clone> Clone for VecProducer<T> {
+    fn clone(&self) -> Self {
+        VecProducer::new(self.as_slice().to_vec())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for VecProducer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VecProducer")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
@@ -0,0 +1,27 @@
+use alloc::collections::VecDeque;
+
+use crate::producer::Producer;
+
+/// Produces items by popping them off the front of the deque.
+///
+/// Producing from an empty deque results in `Err(())`. Items can be pushed onto the back of the
+/// deque at any time, including after an internal state change, and will be produced
+/// afterwards. `stop` drops all items and releases the deque's memory.
+impl<T> Producer for VecDeque<T> {
+    type Item = T;
+    type Ex = ();
+    type In = ();
+
+    fn produce(&mut self) -> Result<T, ()> {
+        self.pop_front().ok_or(())
+    }
+
+    fn slurp(&mut self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), ()> {
+        *self = VecDeque::new();
+        Ok(())
+    }
+}
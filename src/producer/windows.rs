@@ -0,0 +1,176 @@
+use core::array;
+use core::fmt;
+use core::mem::MaybeUninit;
+
+use crate::producer::Producer;
+
+/// Produces all overlapping windows of `N` consecutive items of the wrapped producer.
+///
+/// The first window is produced once the wrapped producer has produced `N` items, every further
+/// window takes one more item from it. The items of the current window are kept in a circular
+/// inline buffer. If the wrapped producer performs an internal state change before the window is
+/// full, it is passed on and the buffered items are kept, so the window can be completed if the
+/// wrapped producer produces more items afterwards. `stop` discards the buffered items.
+pub struct WindowsProducer<P: Producer, const N: usize> {
+    producer: P,
+    // The `len` items starting at `start` (wrapping around) are initialized.
+    buf: [MaybeUninit<P::Item>; N],
+    start: usize,
+    len: usize,
+}
+
+impl<P: Producer, const N: usize> WindowsProducer<P, N> {
+    /// Panics if `N` is zero.
+    pub fn new(producer: P) -> Self {
+        assert!(N > 0, "WindowsProducer requires a nonzero window size");
+        WindowsProducer {
+            producer,
+            buf: [const { MaybeUninit::uninit() }; N],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns how many items of the next window have already been buffered.
+    pub fn buffered(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the wrapped producer, discarding the buffered items.
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+impl<P, const N: usize> Producer for WindowsProducer<P, N>
+where
+    P: Producer,
+    P::Item: Copy,
+{
+    type Item = [P::Item; N];
+    type Ex = P::Ex;
+    type In = P::In;
+
+    fn produce(&mut self) -> Result<[P::Item; N], P::In> {
+        while self.len < N {
+            let item = self.producer.produce()?;
+            self.buf[(self.start + self.len) % N] = MaybeUninit::new(item);
+            self.len += 1;
+        }
+
+        let window = array::from_fn(|i| unsafe { self.buf[(self.start + i) % N].assume_init() });
+        self.start = (self.start + 1) % N;
+        self.len -= 1;
+        Ok(window)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.len = 0;
+        self.producer.stop(reason)
+    }
+}
+
+impl<P, const N: usize> Clone for WindowsProducer<P, N>
+where
+    P: Producer + Clone,
+    P::Item: Copy,
+{
+    fn clone(&self) -> Self {
+        WindowsProducer {
+            producer: self.producer.clone(),
+            buf: self.buf,
+            start: self.start,
+            len: self.len,
+        }
+    }
+}
+
+impl<P, const N: usize> fmt::Debug for WindowsProducer<P, N>
+where
+    P: Producer + fmt::Debug,
+    P::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowsProducer")
+            .field("producer", &self.producer)
+            .field("buffer", &BufferDebug(self))
+            .finish()
+    }
+}
+
+struct BufferDebug<'a, P: Producer, const N: usize>(&'a WindowsProducer<P, N>);
+
+impl<'a, P, const N: usize> fmt::Debug for BufferDebug<'a, P, N>
+where
+    P: Producer,
+    P::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let w = self.0;
+        f.debug_list()
+            .entries((0..w.len).map(|i| unsafe { w.buf[(w.start + i) % N].assume_init_ref() }))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    fn windows<const N: usize>(items: &[u8]) -> Vec<[u8; N]> {
+        let mut producer = WindowsProducer::<_, N>::new(items);
+        let mut windows = Vec::new();
+        while let Ok(window) = producer.produce() {
+            windows.push(window);
+        }
+        windows
+    }
+
+    #[test]
+    fn size_one() {
+        assert_eq!(windows::<1>(&[1, 2, 3]), [[1], [2], [3]]);
+    }
+
+    #[test]
+    fn size_of_input() {
+        assert_eq!(windows::<3>(&[1, 2, 3]), [[1, 2, 3]]);
+    }
+
+    #[test]
+    fn input_shorter_than_window() {
+        assert!(windows::<4>(&[1, 2, 3]).is_empty());
+        assert!(windows::<2>(&[]).is_empty());
+    }
+
+    #[test]
+    fn order_after_wrapping_around() {
+        let items: Vec<u8> = (0..10).collect();
+        let expected: Vec<[u8; 3]> = items.windows(3).map(|w| [w[0], w[1], w[2]]).collect();
+        assert_eq!(windows::<3>(&items), expected);
+    }
+
+    #[test]
+    fn completes_window_after_state_change() {
+        let inner = ScriptedProducer::<u8, (), ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::StateChange(()),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::Yield(3),
+            ProduceOutcome::StateChange(()),
+        ]);
+        let mut producer = WindowsProducer::<_, 2>::new(inner);
+        assert_eq!(producer.produce(), Err(()));
+        assert_eq!(producer.buffered(), 1);
+        assert_eq!(producer.produce(), Ok([1, 2]));
+        assert_eq!(producer.produce(), Ok([2, 3]));
+        assert_eq!(producer.produce(), Err(()));
+    }
+}
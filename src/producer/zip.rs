@@ -0,0 +1,108 @@
+use crate::producer::Producer;
+use crate::Either;
+
+/// Produces pairs of items of two producers.
+///
+/// If the first producer produces an item but the second one performs an internal state change,
+/// the item of the first producer is kept and used for the next pair, so no item is lost.
+#[derive(Debug, Clone)]
+pub struct ZipProducer<A: Producer, B> {
+    a: A,
+    b: B,
+    pending: Option<A::Item>,
+}
+
+impl<A: Producer, B> ZipProducer<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        ZipProducer {
+            a,
+            b,
+            pending: None,
+        }
+    }
+
+    /// Returns the wrapped producers. An item of the first producer that is being held back
+    /// because the second one performed an internal state change is dropped, use
+    /// [`into_parts`](ZipProducer::into_parts) to recover it.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+
+    /// Returns the wrapped producers together with the item of the first producer that is being
+    /// held back for the next pair, if any.
+    pub fn into_parts(self) -> (A, B, Option<A::Item>) {
+        (self.a, self.b, self.pending)
+    }
+}
+
+impl<A: Producer, B: Producer> Producer for ZipProducer<A, B> {
+    type Item = (A::Item, B::Item);
+    type Ex = (A::Ex, B::Ex);
+    type In = Either<A::In, B::In>;
+
+    fn produce(&mut self) -> Result<Self::Item, Self::In> {
+        let a = match self.pending.take() {
+            Some(a) => a,
+            None => self.a.produce().map_err(Either::Left)?,
+        };
+
+        match self.b.produce() {
+            Ok(b) => Ok((a, b)),
+            Err(e) => {
+                self.pending = Some(a);
+                Err(Either::Right(e))
+            }
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Self::In> {
+        self.a.slurp().map_err(Either::Left)?;
+        self.b.slurp().map_err(Either::Right)
+    }
+
+    /// Stops both producers, even if stopping the first one fails.
+    fn stop(&mut self, (reason_a, reason_b): Self::Ex) -> Result<(), Self::In> {
+        let a = self.a.stop(reason_a).map_err(Either::Left);
+        let b = self.b.stop(reason_b).map_err(Either::Right);
+        a.and(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::{ProduceOutcome, ScriptedProducer};
+
+    #[test]
+    fn keeps_first_item_when_second_fails() {
+        let a = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(1),
+            ProduceOutcome::Yield(2),
+            ProduceOutcome::PanicIfCalled,
+        ]);
+        let b = ScriptedProducer::<u8, &str, ()>::new([
+            ProduceOutcome::Yield(10),
+            ProduceOutcome::StateChange("b"),
+        ]);
+        let mut producer = ZipProducer::new(a, b);
+
+        assert_eq!(producer.produce(), Ok((1, 10)));
+        assert_eq!(producer.produce(), Err(Either::Right("b")));
+
+        let (_, _, pending) = producer.into_parts();
+        assert_eq!(pending, Some(2));
+    }
+
+    #[test]
+    fn into_inner_returns_both_producers() {
+        let a = ScriptedProducer::<u8, (), ()>::new([ProduceOutcome::Yield(1)]);
+        let b = ScriptedProducer::<u8, (), ()>::new([ProduceOutcome::Yield(2)]);
+        let mut producer = ZipProducer::new(a, b);
+
+        assert_eq!(producer.produce(), Ok((1, 2)));
+        let (a, b) = producer.into_inner();
+        assert_eq!(a.log().len(), 1);
+        assert_eq!(b.log().len(), 1);
+    }
+}
@@ -0,0 +1,296 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::consumer::Consumer;
+use crate::producer::Producer;
+
+/// The internal state change of an [`SpscProducer`] whose queue holds no items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpscEmpty;
+
+/// The internal state change of an [`SpscConsumer`] whose queue has no free capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpscFull;
+
+/// A fixed-capacity single-producer single-consumer ring buffer that does not allocate.
+///
+/// The queue is accessed through the [`SpscConsumer`] (which moves items in) and the
+/// [`SpscProducer`] (which moves them out) obtained from [`split`](SpscQueue::split). The two
+/// halves may live on different threads.
+pub struct SpscQueue<T, const N: usize> {
+    buf: [UnsafeCell<MaybeUninit<T>>; N],
+    // Both indices only ever increase (wrapping), `tail - head` is the number of items.
+    // The slots in `head..tail` (modulo `N`) are initialized.
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+unsafe impl<T: Send, const N: usize> Sync for SpscQueue<T, N> {}
+
+impl<T, const N: usize> SpscQueue<T, N> {
+    /// Creates an empty queue.
+    ///
+    /// Panics if `N` is zero.
+    pub const fn new() -> Self {
+        assert!(N > 0, "SpscQueue requires a nonzero capacity");
+        SpscQueue {
+            buf: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the queue into the producer that moves items out of it and the consumer that moves
+    /// items into it.
+    pub fn split(&mut self) -> (SpscProducer<'_, T, N>, SpscConsumer<'_, T, N>) {
+        (SpscProducer { queue: self }, SpscConsumer { queue: self })
+    }
+
+    /// Returns how many items are currently in the queue. Other threads may change this value at
+    /// any time.
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    // Must only be called from the single `SpscProducer`.
+    fn pop(&self) -> Result<T, SpscEmpty> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return Err(SpscEmpty);
+        }
+
+        let item = unsafe { (*self.buf[head % N].get()).assume_init_read() };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Ok(item)
+    }
+
+    // Must only be called from the single `SpscConsumer`.
+    fn push(&self, item: T) -> Result<(), SpscFull> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.head.load(Ordering::Acquire)) == N {
+            return Err(SpscFull);
+        }
+
+        unsafe { (*self.buf[tail % N].get()).write(item) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Default for SpscQueue<T, N> {
+    fn default() -> Self {
+        SpscQueue::new()
+    }
+}
+
+impl<T, const N: usize> Drop for SpscQueue<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_ok() {}
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for SpscQueue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscQueue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+/// Produces the items of an [`SpscQueue`], returning `Err(SpscEmpty)` while the queue is empty.
+///
+/// The producer stays usable after an internal state change, `produce` succeeds again once the
+/// consumer has moved in more items. `slurp` and `stop` do not do anything.
+#[derive(Debug)]
+pub struct SpscProducer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+}
+
+impl<T, const N: usize> SpscProducer<'_, T, N> {
+    /// Returns how many items are currently available.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+impl<T, const N: usize> Producer for SpscProducer<'_, T, N> {
+    type Item = T;
+    type Ex = ();
+    type In = SpscEmpty;
+
+    fn produce(&mut self) -> Result<T, SpscEmpty> {
+        self.queue.pop()
+    }
+
+    fn slurp(&mut self) -> Result<(), SpscEmpty> {
+        Ok(())
+    }
+
+    fn stop(&mut self, _reason: ()) -> Result<(), SpscEmpty> {
+        Ok(())
+    }
+}
+
+/// Consumes items by moving them into an [`SpscQueue`], returning `Err(SpscFull)` while the queue
+/// is full.
+///
+/// The consumer stays usable after an internal state change, `consume` succeeds again once the
+/// producer has moved out some items. `flush` and `close` do not do anything.
+#[derive(Debug)]
+pub struct SpscConsumer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+}
+
+impl<T, const N: usize> SpscConsumer<'_, T, N> {
+    /// Returns how many more items currently fit into the queue.
+    pub fn free_capacity(&self) -> usize {
+        N - self.queue.len()
+    }
+}
+
+impl<T, const N: usize> Consumer for SpscConsumer<'_, T, N> {
+    type Item = T;
+    type Ex = ();
+    type In = SpscFull;
+
+    fn consume(&mut self, item: T) -> Result<(), SpscFull> {
+        self.queue.push(item)
+    }
+
+    fn flush(&mut self) -> Result<(), SpscFull> {
+        Ok(())
+    }
+
+    fn close(&mut self, _reason: ()) -> Result<(), SpscFull> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let mut queue = SpscQueue::<u8, 2>::new();
+        let (mut producer, _consumer) = queue.split();
+        assert!(producer.is_empty());
+        assert_eq!(producer.produce(), Err(SpscEmpty));
+        assert_eq!(producer.produce(), Err(SpscEmpty));
+    }
+
+    #[test]
+    fn full() {
+        let mut queue = SpscQueue::<u8, 2>::new();
+        let (mut producer, mut consumer) = queue.split();
+        assert_eq!(consumer.consume(1), Ok(()));
+        assert_eq!(consumer.consume(2), Ok(()));
+        assert_eq!(consumer.free_capacity(), 0);
+        assert_eq!(consumer.consume(3), Err(SpscFull));
+
+        assert_eq!(producer.produce(), Ok(1));
+        assert_eq!(consumer.consume(3), Ok(()));
+        assert_eq!(producer.produce(), Ok(2));
+        assert_eq!(producer.produce(), Ok(3));
+        assert_eq!(producer.produce(), Err(SpscEmpty));
+    }
+
+    #[test]
+    fn wraparound() {
+        let mut queue = SpscQueue::<usize, 3>::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut next_in = 0;
+        let mut next_out = 0;
+
+        // Interleave batches of different sizes so that the indices wrap around the buffer at
+        // every possible offset.
+        for round in 0..100 {
+            let batch = round % 4;
+            for _ in 0..batch {
+                match consumer.consume(next_in) {
+                    Ok(()) => next_in += 1,
+                    Err(SpscFull) => assert_eq!(producer.len(), 3),
+                }
+            }
+            for _ in 0..(round + 1) % 3 {
+                match producer.produce() {
+                    Ok(item) => {
+                        assert_eq!(item, next_out);
+                        next_out += 1;
+                    }
+                    Err(SpscEmpty) => assert_eq!(next_out, next_in),
+                }
+            }
+            assert_eq!(producer.len(), next_in - next_out);
+        }
+        while let Ok(item) = producer.produce() {
+            assert_eq!(item, next_out);
+            next_out += 1;
+        }
+        assert_eq!(next_out, next_in);
+    }
+
+    #[test]
+    fn drops_remaining_items() {
+        use alloc::rc::Rc;
+
+        let item = Rc::new(());
+        {
+            let mut queue = SpscQueue::<Rc<()>, 4>::new();
+            let (mut producer, mut consumer) = queue.split();
+            for _ in 0..4 {
+                consumer.consume(item.clone()).unwrap();
+            }
+            drop(producer.produce().unwrap());
+            assert_eq!(Rc::strong_count(&item), 4);
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn two_threads() {
+        const COUNT: u32 = 10_000;
+
+        let mut queue = SpscQueue::<u32, 8>::new();
+        let (mut producer, mut consumer) = queue.split();
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..COUNT {
+                    while consumer.consume(i).is_err() {
+                        std::thread::yield_now();
+                    }
+                }
+            });
+            s.spawn(move || {
+                let mut expected = 0;
+                while expected < COUNT {
+                    match producer.produce() {
+                        Ok(item) => {
+                            assert_eq!(item, expected);
+                            expected += 1;
+                        }
+                        Err(SpscEmpty) => std::thread::yield_now(),
+                    }
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,206 @@
+use core::cell::Cell;
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::consumer::Consumer;
+use crate::producer::Producer;
+
+/// What a [`ScriptedProducer`] does when `produce` is called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProduceOutcome<T, In> {
+    /// Produce the item.
+    Yield(T),
+    /// Perform an internal state change.
+    StateChange(In),
+    /// Panic, because the code under test must not call `produce` at this point.
+    PanicIfCalled,
+}
+
+/// A method call received by a [`ScriptedProducer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProducerCall<Ex> {
+    Produce,
+    Slurp,
+    Stop(Ex),
+}
+
+/// A producer that replays a script of outcomes and records all method calls it receives.
+///
+/// Calling `produce` after the script has been exhausted panics. `slurp` replays a separate
+/// script of results and succeeds once that one has been exhausted, `stop` always succeeds.
+#[derive(Debug, Clone)]
+pub struct ScriptedProducer<T, In, Ex = ()> {
+    script: VecDeque<ProduceOutcome<T, In>>,
+    slurp_script: VecDeque<Result<(), In>>,
+    log: Vec<ProducerCall<Ex>>,
+}
+
+impl<T, In, Ex> ScriptedProducer<T, In, Ex> {
+    pub fn new<I: IntoIterator<Item = ProduceOutcome<T, In>>>(script: I) -> Self {
+        ScriptedProducer {
+            script: script.into_iter().collect(),
+            slurp_script: VecDeque::new(),
+            log: Vec::new(),
+        }
+    }
+
+    /// Sets the results of the next calls to `slurp`.
+    pub fn with_slurp<I: IntoIterator<Item = Result<(), In>>>(mut self, script: I) -> Self {
+        self.slurp_script = script.into_iter().collect();
+        self
+    }
+
+    /// Returns all method calls received so far, in order.
+    pub fn log(&self) -> &[ProducerCall<Ex>] {
+        &self.log
+    }
+
+    /// Returns how many outcomes of the `produce` script have not been used yet.
+    pub fn remaining_script(&self) -> usize {
+        self.script.len()
+    }
+}
+
+impl<T, In, Ex> Producer for ScriptedProducer<T, In, Ex> {
+    type Item = T;
+    type Ex = Ex;
+    type In = In;
+
+    fn produce(&mut self) -> Result<T, In> {
+        self.log.push(ProducerCall::Produce);
+        match self.script.pop_front() {
+            Some(ProduceOutcome::Yield(item)) => Ok(item),
+            Some(ProduceOutcome::StateChange(e)) => Err(e),
+            Some(ProduceOutcome::PanicIfCalled) => {
+                panic!("ScriptedProducer: produce was called where the script forbids it")
+            }
+            None => panic!("ScriptedProducer: produce was called after the script ended"),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), In> {
+        self.log.push(ProducerCall::Slurp);
+        self.slurp_script.pop_front().unwrap_or(Ok(()))
+    }
+
+    fn stop(&mut self, reason: Ex) -> Result<(), In> {
+        self.log.push(ProducerCall::Stop(reason));
+        Ok(())
+    }
+}
+
+/// What a [`ScriptedConsumer`] does when one of its methods is called.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsumerResponse<In> {
+    /// Succeed.
+    Accept,
+    /// Perform an internal state change.
+    StateChange(In),
+}
+
+/// A method call received by a [`ScriptedConsumer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsumerCall<T, Ex> {
+    Consume(T),
+    Flush,
+    Close(Ex),
+}
+
+/// A consumer that answers method calls from a script of responses and records all method calls
+/// it receives, including the consumed items.
+///
+/// Every call to `consume`, `flush` or `close` uses the next response of the script, all calls
+/// succeed once the script has been exhausted. Calling any method after an internal state change
+/// or after `close` panics, so tests fail if the code under test keeps using the consumer.
+#[derive(Debug, Clone)]
+pub struct ScriptedConsumer<T, In, Ex = ()> {
+    script: VecDeque<ConsumerResponse<In>>,
+    log: Vec<ConsumerCall<T, Ex>>,
+    done: bool,
+}
+
+impl<T, In, Ex> ScriptedConsumer<T, In, Ex> {
+    pub fn new<I: IntoIterator<Item = ConsumerResponse<In>>>(script: I) -> Self {
+        ScriptedConsumer {
+            script: script.into_iter().collect(),
+            log: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Returns all method calls received so far, in order.
+    pub fn log(&self) -> &[ConsumerCall<T, Ex>] {
+        &self.log
+    }
+
+    /// Returns how many responses of the script have not been used yet.
+    pub fn remaining_script(&self) -> usize {
+        self.script.len()
+    }
+
+    fn respond(&mut self, call: ConsumerCall<T, Ex>) -> Result<(), In> {
+        if self.done {
+            panic!("ScriptedConsumer: method was called after an internal state change or close");
+        }
+
+        let close = matches!(call, ConsumerCall::Close(_));
+        self.log.push(call);
+        match self.script.pop_front() {
+            Some(ConsumerResponse::StateChange(e)) => {
+                self.done = true;
+                Err(e)
+            }
+            Some(ConsumerResponse::Accept) | None => {
+                self.done = close;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T, In, Ex> Consumer for ScriptedConsumer<T, In, Ex> {
+    type Item = T;
+    type Ex = Ex;
+    type In = In;
+
+    fn consume(&mut self, item: T) -> Result<(), In> {
+        self.respond(ConsumerCall::Consume(item))
+    }
+
+    fn flush(&mut self) -> Result<(), In> {
+        self.respond(ConsumerCall::Flush)
+    }
+
+    fn close(&mut self, reason: Ex) -> Result<(), In> {
+        self.respond(ConsumerCall::Close(reason))
+    }
+}
+
+/// A value that increments a shared counter when it is dropped, for checking that code which
+/// manages uninitialized memory drops every item exactly once.
+///
+/// Values compare equal if their wrapped values are equal, regardless of the counter.
+#[derive(Debug)]
+pub struct DropCounted<'a, T> {
+    pub value: T,
+    drops: &'a Cell<usize>,
+}
+
+impl<'a, T> DropCounted<'a, T> {
+    pub fn new(value: T, drops: &'a Cell<usize>) -> Self {
+        DropCounted { value, drops }
+    }
+}
+
+impl<T> Drop for DropCounted<'_, T> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+impl<T: PartialEq> PartialEq for DropCounted<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
@@ -0,0 +1,30 @@
+/// A value of one of two types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+// The number of bytes of a `char` starting with the given byte, or `None` if no `char` can start
+// with it.
+pub(crate) fn utf8_width(first: u8) -> Option<usize> {
+    match first {
+        0x00..=0x7F => Some(1),
+        0xC2..=0xDF => Some(2),
+        0xE0..=0xEF => Some(3),
+        0xF0..=0xF4 => Some(4),
+        _ => None,
+    }
+}
+
+// Whether `byte` can follow the bytes in `prefix` (which must be nonempty) in a valid `char`.
+pub(crate) fn is_utf8_continuation(prefix: &[u8], byte: u8) -> bool {
+    let range = match (prefix.len(), prefix[0]) {
+        (1, 0xE0) => 0xA0..=0xBF,
+        (1, 0xED) => 0x80..=0x9F,
+        (1, 0xF0) => 0x90..=0xBF,
+        (1, 0xF4) => 0x80..=0x8F,
+        _ => 0x80..=0xBF,
+    };
+    range.contains(&byte)
+}
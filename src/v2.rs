@@ -0,0 +1,184 @@
+use crate::producer::Producer;
+
+/// The outcome of [`Producer2::produce`], distinguishing the regular end of a sequence from an
+/// error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProducerResult<T, E> {
+    /// The next item of the sequence.
+    Item(T),
+    /// The sequence has ended regularly.
+    EndOfStream,
+    /// An error occurred.
+    Error(E),
+}
+
+impl<T, E> ProducerResult<T, E> {
+    /// Converts the outcome of a [`Producer`] method, using `is_end` to decide whether an internal
+    /// state change is the end of the sequence or an error.
+    pub fn from_v1<F: FnOnce(&E) -> bool>(result: Result<T, E>, is_end: F) -> Self {
+        match result {
+            Ok(item) => ProducerResult::Item(item),
+            Err(e) if is_end(&e) => ProducerResult::EndOfStream,
+            Err(e) => ProducerResult::Error(e),
+        }
+    }
+
+    /// Converts into a `Result` that is `Ok(None)` at the end of the sequence.
+    pub fn into_result(self) -> Result<Option<T>, E> {
+        match self {
+            ProducerResult::Item(item) => Ok(Some(item)),
+            ProducerResult::EndOfStream => Ok(None),
+            ProducerResult::Error(e) => Err(e),
+        }
+    }
+
+    pub fn is_item(&self) -> bool {
+        matches!(self, ProducerResult::Item(_))
+    }
+
+    pub fn is_end_of_stream(&self) -> bool {
+        matches!(self, ProducerResult::EndOfStream)
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, ProducerResult::Error(_))
+    }
+}
+
+/// A sequence manipulator that moves items out of a sequence, front to back, and reports the end
+/// of the sequence separately from errors.
+///
+/// This is the experimental successor of [`Producer`], whose `In` type has to cover both. Use
+/// [`FromV1Producer`] and [`IntoV1Producer`] to convert between the two while the design
+/// stabilizes. The same rules about calling methods after the end, an error or `stop` apply.
+pub trait Producer2 {
+    /// The type of the items in the sequence.
+    type Item;
+    /// Information about why `stop` is being called.
+    type Ex;
+    /// Information about an error.
+    type Error;
+
+    /// Moves the item at the current position out of the sequence and advances the position.
+    fn produce(&mut self) -> ProducerResult<Self::Item, Self::Error>;
+
+    /// Moves items into an internal buffer, even if that buffer is not empty.
+    fn slurp(&mut self) -> Result<(), Self::Error>;
+
+    /// Notifies the producer that `produce` will not be called anymore.
+    fn stop(&mut self, reason: Self::Ex) -> Result<(), Self::Error>;
+}
+
+impl<P: Producer2 + ?Sized> Producer2 for &mut P {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type Error = P::Error;
+
+    fn produce(&mut self) -> ProducerResult<P::Item, P::Error> {
+        (**self).produce()
+    }
+
+    fn slurp(&mut self) -> Result<(), P::Error> {
+        (**self).slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::Error> {
+        (**self).stop(reason)
+    }
+}
+
+/// Implements [`Producer2`] for a [`Producer`], using a function to decide whether an internal
+/// state change is the end of the sequence or an error.
+///
+/// Internal state changes of `slurp` and `stop` are always reported as errors.
+#[derive(Debug, Clone)]
+pub struct FromV1Producer<P, F> {
+    producer: P,
+    is_end: F,
+}
+
+impl<P, F> FromV1Producer<P, F> {
+    pub fn new(producer: P, is_end: F) -> Self {
+        FromV1Producer { producer, is_end }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+/// Creates a [`Producer2`] from a [`Producer`], treating the internal state changes for which
+/// `is_end` returns `true` as the end of the sequence.
+pub fn from_v1<P, F>(producer: P, is_end: F) -> FromV1Producer<P, F>
+where
+    P: Producer,
+    F: FnMut(&P::In) -> bool,
+{
+    FromV1Producer::new(producer, is_end)
+}
+
+impl<P, F> Producer2 for FromV1Producer<P, F>
+where
+    P: Producer,
+    F: FnMut(&P::In) -> bool,
+{
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type Error = P::In;
+
+    fn produce(&mut self) -> ProducerResult<P::Item, P::In> {
+        ProducerResult::from_v1(self.producer.produce(), &mut self.is_end)
+    }
+
+    fn slurp(&mut self) -> Result<(), P::In> {
+        self.producer.slurp()
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), P::In> {
+        self.producer.stop(reason)
+    }
+}
+
+/// Implements [`Producer`] for a [`Producer2`], with `None` as the internal state change for the
+/// end of the sequence and `Some(error)` for an error.
+#[derive(Debug, Clone)]
+pub struct IntoV1Producer<P> {
+    producer: P,
+}
+
+impl<P> IntoV1Producer<P> {
+    pub fn new(producer: P) -> Self {
+        IntoV1Producer { producer }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.producer
+    }
+}
+
+/// Creates a [`Producer`] from a [`Producer2`].
+pub fn into_v1<P: Producer2>(producer: P) -> IntoV1Producer<P> {
+    IntoV1Producer::new(producer)
+}
+
+impl<P: Producer2> Producer for IntoV1Producer<P> {
+    type Item = P::Item;
+    type Ex = P::Ex;
+    type In = Option<P::Error>;
+
+    fn produce(&mut self) -> Result<P::Item, Option<P::Error>> {
+        match self.producer.produce() {
+            ProducerResult::Item(item) => Ok(item),
+            ProducerResult::EndOfStream => Err(None),
+            ProducerResult::Error(e) => Err(Some(e)),
+        }
+    }
+
+    fn slurp(&mut self) -> Result<(), Option<P::Error>> {
+        self.producer.slurp().map_err(Some)
+    }
+
+    fn stop(&mut self, reason: P::Ex) -> Result<(), Option<P::Error>> {
+        self.producer.stop(reason).map_err(Some)
+    }
+}